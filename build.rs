@@ -4,8 +4,8 @@ fn main() {
    // Build our realtime feed structure
    Codegen::new()
       .out_dir("src/yahoo")
-      .inputs(&["src/yahoo/realtime.proto"])
-      .includes(&[ "src" ])
+      .inputs(["src/yahoo/realtime.proto"])
+      .includes([ "src" ])
       .customize(Customize { ..Default::default() })
       .run()
       .expect("Codegen failed.");
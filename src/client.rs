@@ -0,0 +1,308 @@
+use once_cell::sync::OnceCell;
+use reqwest::header::HeaderMap;
+use reqwest::{Proxy, Url};
+use snafu::ResultExt;
+use std::time::Duration;
+
+use crate::{error, history::History, options::Options, retry, search::Search, Profile, RateLimiter, Result, RetryPolicy, Snapshot, Streamer};
+use crate::error::InnerError;
+use crate::streaming::STREAMING_URL;
+
+const CHART_URL: &str = "https://query1.finance.yahoo.com/v8/finance/chart/";
+const OPTIONS_URL: &str = "https://query1.finance.yahoo.com/v7/finance/options/";
+const PROFILE_URL: &str = "https://finance.yahoo.com";
+const QUOTE_URL: &str = "https://query1.finance.yahoo.com/v7/finance/quote";
+const SEARCH_URL: &str = "https://query1.finance.yahoo.com/v1/finance/search";
+
+/// The client behind the free functions - unless one is installed with `Client::set_default`
+static SHARED: OnceCell<Client> = OnceCell::new();
+
+/// The client behind the free functions - ie. [`history::retrieve`](history/fn.retrieve.html) -
+/// which is created on first use so that they all share one connection pool.  Unless another
+/// client was installed with [`Client::set_default`](struct.Client.html#method.set_default),
+/// it uses the default retry policy and no rate limit.
+pub(crate) fn shared() -> Result<&'static Client> {
+   SHARED.get_or_try_init(Client::new)
+}
+
+/// Parses an endpoint making sure that it always ends in a `/` so that we can join paths onto it
+fn parse_endpoint(url: &str) -> Result<Url> {
+   let endpoint = if url.ends_with('/') { url.to_string() } else { format!("{}/", url) };
+   Ok(Url::parse(&endpoint).context(error::InvalidURL { url })?)
+}
+
+/// Builds up a [`Client`](struct.Client.html) with a custom configuration.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use yahoo_finance::Client;
+///
+/// let client = Client::builder()
+///    .timeout(Duration::from_secs(10))
+///    .user_agent("my-app/1.0")
+///    .build()
+///    .unwrap();
+/// ```
+pub struct ClientBuilder {
+   http: reqwest::ClientBuilder,
+   retry: RetryPolicy,
+   limiter: Option<RateLimiter>,
+   chart_url: String,
+   options_url: String,
+   profile_url: String,
+   quote_url: String,
+   search_url: String,
+   streaming_url: String
+}
+impl ClientBuilder {
+   /// Sets the base URL used to load charts - ie. history
+   pub fn chart_url(mut self, url: &str) -> ClientBuilder {
+      self.chart_url = url.to_string();
+      self
+   }
+
+   /// Sets the base URL used to load option chains
+   pub fn options_url(mut self, url: &str) -> ClientBuilder {
+      self.options_url = url.to_string();
+      self
+   }
+
+   /// Sets the base URL used to load symbol profiles
+   pub fn profile_url(mut self, url: &str) -> ClientBuilder {
+      self.profile_url = url.to_string();
+      self
+   }
+
+   /// Sets the URL used to load snapshot quotes
+   pub fn quote_url(mut self, url: &str) -> ClientBuilder {
+      self.quote_url = url.to_string();
+      self
+   }
+
+   /// Sets the URL used to search for symbols
+   pub fn search_url(mut self, url: &str) -> ClientBuilder {
+      self.search_url = url.to_string();
+      self
+   }
+
+   /// Sets the websocket URL used to stream realtime quotes
+   pub fn streaming_url(mut self, url: &str) -> ClientBuilder {
+      self.streaming_url = url.to_string();
+      self
+   }
+
+   /// Sets the timeout for an entire request - from connecting until the body has been read
+   pub fn timeout(mut self, timeout: Duration) -> ClientBuilder {
+      self.http = self.http.timeout(timeout);
+      self
+   }
+
+   /// Sets the timeout for connecting to Yahoo!
+   pub fn connect_timeout(mut self, timeout: Duration) -> ClientBuilder {
+      self.http = self.http.connect_timeout(timeout);
+      self
+   }
+
+   /// Sets the `User-Agent` header sent with every request
+   pub fn user_agent(mut self, user_agent: &str) -> ClientBuilder {
+      self.http = self.http.user_agent(user_agent);
+      self
+   }
+
+   /// Sets headers that are sent with every request
+   pub fn default_headers(mut self, headers: HeaderMap) -> ClientBuilder {
+      self.http = self.http.default_headers(headers);
+      self
+   }
+
+   /// Sends all requests through a proxy
+   pub fn proxy(mut self, proxy: Proxy) -> ClientBuilder {
+      self.http = self.http.proxy(proxy);
+      self
+   }
+
+   /// Sets the maximum number of idle connections kept in the pool for each host
+   pub fn pool_max_idle_per_host(mut self, max: usize) -> ClientBuilder {
+      self.http = self.http.pool_max_idle_per_host(max);
+      self
+   }
+
+   /// Sets how calls that fail for a transient reason are retried - by default they are not
+   pub fn retry(mut self, policy: RetryPolicy) -> ClientBuilder {
+      self.retry = policy;
+      self
+   }
+
+   /// Limits how quickly calls are made - every endpoint (and every retry) shares the limit
+   pub fn rate_limit(mut self, limiter: RateLimiter) -> ClientBuilder {
+      self.limiter = Some(limiter);
+      self
+   }
+
+   /// Creates the client
+   pub fn build(self) -> Result<Client> {
+      let streaming_url = Url::parse(&self.streaming_url).context(error::InvalidURL { url: &self.streaming_url })?;
+
+      Ok(Client {
+         http: self.http.build().context(error::BuildClient)?,
+         retry: self.retry,
+         limiter: self.limiter,
+         chart_url: parse_endpoint(&self.chart_url)?,
+         options_url: parse_endpoint(&self.options_url)?,
+         profile_url: parse_endpoint(&self.profile_url)?,
+         quote_url: Url::parse(&self.quote_url).context(error::InvalidURL { url: &self.quote_url })?,
+         search_url: Url::parse(&self.search_url).context(error::InvalidURL { url: &self.search_url })?,
+         streaming_url: streaming_url.to_string()
+      })
+   }
+}
+
+/// A client for calling Yahoo!
+///
+/// All calls made through a client share its configuration and connection pool.  Cloning
+/// a client is cheap and the clones continue to share the connection pool.
+///
+/// # Examples
+///
+/// ```no_run
+/// use yahoo_finance::{ Client, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let client = Client::new().unwrap();
+///
+///    let bars = client.history().retrieve_interval("AAPL", Interval::_1mo).await.unwrap();
+///    let profile = client.profile("AAPL").await.unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Client {
+   http: reqwest::Client,
+   retry: RetryPolicy,
+   limiter: Option<RateLimiter>,
+   chart_url: Url,
+   options_url: Url,
+   profile_url: Url,
+   quote_url: Url,
+   search_url: Url,
+   streaming_url: String
+}
+impl Client {
+   /// Creates a client with the default configuration - which fails if the
+   /// underlying HTTP client cannot be initialized
+   pub fn new() -> Result<Client> {
+      Client::builder().build()
+   }
+
+   /// Makes the free functions - ie. [`history::retrieve`](history/fn.retrieve.html) - use
+   /// this client, so they share its retry policy and rate limit.
+   ///
+   /// This has to happen before the first call to a free function - after that the
+   /// default client is fixed, and this returns `false` without changing it.
+   ///
+   /// # Examples
+   ///
+   /// ``` no_run
+   /// use yahoo_finance::{history, Client, RateLimiter};
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let client = Client::builder().rate_limit(RateLimiter::new(2.0, 5)).build().unwrap();
+   ///    assert!(Client::set_default(client));
+   ///
+   ///    // now limited to 2 calls a second
+   ///    let bars = history::retrieve("AAPL").await.unwrap();
+   /// }
+   /// ```
+   pub fn set_default(client: Client) -> bool {
+      SHARED.set(client).is_ok()
+   }
+
+   /// Creates a builder to configure a client
+   pub fn builder() -> ClientBuilder {
+      ClientBuilder {
+         http: reqwest::Client::builder(),
+         retry: RetryPolicy::none(),
+         limiter: None,
+         chart_url: CHART_URL.to_string(),
+         options_url: OPTIONS_URL.to_string(),
+         profile_url: PROFILE_URL.to_string(),
+         quote_url: QUOTE_URL.to_string(),
+         search_url: SEARCH_URL.to_string(),
+         streaming_url: STREAMING_URL.to_string()
+      }
+   }
+
+   /// Historical quotes using this client
+   pub fn history(&self) -> History<'_> { History::new(self) }
+
+   /// Option chains using this client
+   pub fn options(&self) -> Options<'_> { Options::new(self) }
+
+   /// Loads the profile for a symbol using this client
+   pub async fn profile(&self, symbol: &str) -> Result<Profile> { Profile::load_with(self, symbol).await }
+
+   /// Symbol search using this client
+   pub fn search(&self) -> Search<'_> { Search::new(self) }
+
+   /// Loads snapshot quotes for many symbols using this client
+   pub async fn snapshots(&self, symbols: &[&str]) -> Result<Vec<Snapshot>> { Snapshot::load_with(self, symbols).await }
+
+   /// Creates a realtime quote streamer that connects using this client's configuration
+   pub fn streamer(&self, symbols: Vec<&str>) -> Streamer { Streamer::with_url(symbols, &self.streaming_url) }
+
+   pub(crate) fn chart_url(&self) -> &Url { &self.chart_url }
+
+   pub(crate) fn options_url(&self) -> &Url { &self.options_url }
+
+   pub(crate) fn profile_url(&self) -> &Url { &self.profile_url }
+
+   pub(crate) fn quote_url(&self) -> &Url { &self.quote_url }
+
+   pub(crate) fn search_url(&self) -> &Url { &self.search_url }
+
+   /// Calls Yahoo! and reads back the body of a successful response - retrying
+   /// according to the client's retry policy
+   pub(crate) async fn fetch(&self, url: &Url) -> Result<String> {
+      let mut attempt = 1;
+      loop {
+         let (error, retry_after) = match self.fetch_once(url).await {
+            Ok(body) => return Ok(body),
+            Err(failure) => failure
+         };
+
+         if attempt >= self.retry.max_attempts || !error.is_retryable() {
+            if attempt == 1 { return Err(error.into()); }
+            return Err(InnerError::RetriesExhausted { attempts: attempt, source: Box::new(error) }.into());
+         }
+
+         tokio::time::delay_for(self.retry.backoff(attempt, retry_after)).await;
+         attempt += 1;
+      }
+   }
+
+   /// Makes a single call to Yahoo! - failures include how long Yahoo! asked us to wait
+   async fn fetch_once(&self, url: &Url) -> std::result::Result<String, (InnerError, Option<Duration>)> {
+      if let Some(limiter) = &self.limiter {
+         if limiter.acquire().await.is_err() { return Err((InnerError::RateLimited, None)); }
+      }
+
+      // make the call - we do not really expect this to fail.
+      // ie - we won't 404 if the symbol doesn't exist
+      let response = self.http.get(url.clone()).send().await
+         .map_err(|source| (InnerError::RequestFailed { source }, None))?;
+
+      if !response.status().is_success() {
+         let status = response.status().as_u16();
+
+         // only a response we'd retry gets to say how long to wait before the retry
+         let retryable = self.retry.retryable_statuses.contains(&status);
+         let retry_after = if retryable { retry::retry_after(response.headers()) } else { None };
+         return Err((InnerError::CallFailed { url: response.url().to_string(), status, retryable }, retry_after));
+      }
+
+      response.text().await.map_err(|source| (InnerError::UnexpectedErrorRead { url: url.to_string(), source }, None))
+   }
+}
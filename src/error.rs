@@ -1,6 +1,6 @@
 use crate::Interval;
-use reqwest;
 use snafu::Snafu;
+use tokio_tungstenite::tungstenite;
 
 /// All possible errors that can occur when using yahoo finance
 #[derive(Debug, Snafu)]
@@ -9,30 +9,78 @@ pub enum InnerError {
    #[snafu(display("Yahoo! returned invalid data - {}", source.to_string()))]
    BadData { source: serde_json::Error },
 
+   #[snafu(display("Unable to create the HTTP client - {}", source.to_string()))]
+   BuildClient { source: reqwest::Error },
+
    #[snafu(display("Yahoo! call failed. '{}' returned a {} result.", url, status))]
-   CallFailed { url: String, status: u16 },
+   CallFailed { url: String, status: u16, retryable: bool },
 
    #[snafu(display("Yahoo! chart failed to load {} - {}.", code, description))]
    ChartFailed { code: String, description: String },
 
+   #[snafu(display("Fell more than {} messages behind the quote hub and was disconnected", capacity))]
+   HubLagged { capacity: usize },
+
+   #[snafu(display("The quote hub's stream failed - {}", reason))]
+   HubUpstream { reason: String },
+
    #[snafu(display("An internal error occurred - please report that '{}'", reason))]
    InternalLogic { reason: String },
 
    #[snafu(display("An internal error occurred - please report that '{}' cannot be parsed because {}", url, source.to_string()))]
    InternalURL { url: String, source: url::ParseError },
 
+   #[snafu(display("Yahoo! only provides {} bars for up to {} days at a time", interval, days))]
+   IntradayTooLong { interval: Interval, days: i64 },
+
+   #[snafu(display("Yahoo! only provides {} bars for the last {} days", interval, days))]
+   IntradayTooOld { interval: Interval, days: i64 },
+
    #[snafu(display("Start date cannot be after the end date"))]
    InvalidStartDate,
 
+   #[snafu(display("'{}' is not a valid URL - {}", url, source.to_string()))]
+   InvalidURL { url: String, source: url::ParseError },
+
    #[snafu(display("Yahoo! returned invalid data - {}", reason))]
    MissingData { reason: String },
 
    #[snafu(display("Intraday intervals like {} are not allowed", interval))]
    NoIntraday { interval: Interval },
 
+   #[snafu(display("Intraday data requires an intraday granularity, not {}", interval))]
+   NotIntraday { interval: Interval },
+
+   #[snafu(display("Yahoo! options failed to load {} - {}.", code, description))]
+   OptionsFailed { code: String, description: String },
+
+   #[snafu(display("Yahoo! quotes failed to load {} - {}.", code, description))]
+   QuoteFailed { code: String, description: String },
+
+   #[snafu(display("Too many calls to Yahoo! - the rate limit has been reached"))]
+   RateLimited,
+
+   #[snafu(display("Unable to use the stream recording '{}' - {}", path, source.to_string()))]
+   Recording { path: String, source: std::io::Error },
+
    #[snafu(display("Yahoo! call failed for unknown reason."))]
    RequestFailed { source: reqwest::Error },
 
+   #[snafu(display("Yahoo! call failed after {} attempts - {}", attempts, source.to_string()))]
+   RetriesExhausted { attempts: u32, source: Box<InnerError> },
+
+   #[snafu(display("Unable to connect to the Yahoo! stream at '{}' - {}", url, source.to_string()))]
+   StreamConnect { url: String, source: tungstenite::Error },
+
+   #[snafu(display("Unable to decode a message from the Yahoo! stream - {}", reason))]
+   StreamDecode { reason: String },
+
+   #[snafu(display("The Yahoo! stream failed - {}", source.to_string()))]
+   StreamProtocol { source: tungstenite::Error },
+
+   #[snafu(display("Nothing was received from the Yahoo! stream for {:?}", timeout))]
+   StreamStale { timeout: std::time::Duration },
+
    #[snafu(display("Unexpected Yahoo! failure. '{}' returned a {}", url, code))]
    UnexectedFailure { url: String, code: u16 },
 
@@ -47,4 +95,17 @@ pub enum InnerError {
 
    #[snafu(display("We currently do not support securities of type '{}'", kind))]
    UnsupportedSecurity { kind: String }
-}
\ No newline at end of file
+}
+impl InnerError {
+   /// Determines if the failure is transient and worth trying again - as the
+   /// client that made the call decided when it failed
+   pub(crate) fn is_retryable(&self) -> bool {
+      match self {
+         InnerError::CallFailed { retryable, .. } => *retryable,
+         InnerError::RequestFailed { source } => source.is_timeout() || source.is_connect() || source.is_request(),
+         InnerError::RetriesExhausted { source, .. } => source.is_retryable(),
+         InnerError::UnexpectedErrorRead { .. } => true,
+         _ => false
+      }
+   }
+}
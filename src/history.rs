@@ -1,9 +1,256 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use futures::{stream, StreamExt};
 use snafu::{ensure, OptionExt};
+use std::collections::{BTreeMap, HashMap};
 
-use crate::{error, yahoo, Bar, Interval, Result};
+use crate::{client, error, yahoo, Bar, Client, Error, Interval, Result, Timestamped};
 
-fn aggregate_bars(data: yahoo::Data) -> Result<Vec<Bar>> {
+/// A bar along with the closing price adjusted for splits and dividends
+#[derive(Clone, Copy, Debug)]
+pub struct AdjustedBar {
+   /// The bar exactly as it traded
+   pub bar: Bar,
+
+   /// The split & dividend adjusted closing price - Yahoo! only provides this for
+   /// daily (or longer) bars
+   pub adjclose: Option<f64>
+}
+impl AdjustedBar {
+   /// Converts this into a bar where the open, high, low and close are all adjusted
+   /// for splits and dividends.  The bar is returned as is if there is no adjusted close.
+   pub fn adjusted(&self) -> Bar {
+      match self.adjclose {
+         Some(adjclose) if self.bar.close != 0.0 => {
+            let ratio = adjclose / self.bar.close;
+            Bar {
+               timestamp: self.bar.timestamp,
+               open: self.bar.open * ratio,
+               high: self.bar.high * ratio,
+               low: self.bar.low * ratio,
+               close: adjclose,
+               volume: self.bar.volume
+            }
+         },
+         _ => self.bar
+      }
+   }
+}
+impl Timestamped for AdjustedBar {
+   /// Gets the timestamp in millisecond accuracy
+   fn timestamp_millis(&self) -> i64 { self.bar.timestamp }
+}
+
+/// The hours for one of the trading sessions in a day
+#[derive(Clone, Debug, PartialEq)]
+pub struct TradingPeriod {
+   /// The abbreviated timezone of the exchange - ie. 'EDT'
+   pub timezone: String,
+
+   /// When the session starts
+   pub start: DateTime<Utc>,
+
+   /// When the session ends
+   pub end: DateTime<Utc>,
+
+   /// The offset from UTC in seconds
+   pub gmt_offset: i64
+}
+impl TradingPeriod {
+   fn new(data: &yahoo::TradingPeriod) -> TradingPeriod {
+      TradingPeriod {
+         timezone: data.timezone.clone(),
+         start: data.start,
+         end: data.end,
+         gmt_offset: data.gmtoffset
+      }
+   }
+}
+
+/// The pre-market, regular and post-market sessions of the current trading day
+#[derive(Clone, Debug, PartialEq)]
+pub struct TradingPeriods {
+   /// The pre-market session before the regular open
+   pub pre: TradingPeriod,
+
+   /// The regular trading session
+   pub regular: TradingPeriod,
+
+   /// The post-market session after the regular close
+   pub post: TradingPeriod
+}
+
+/// Information about a symbol that Yahoo! returns along with its history
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChartMeta {
+   /// The symbol the history is for
+   pub symbol: String,
+
+   /// The currency that prices are in - ie. 'USD'
+   pub currency: Option<String>,
+
+   /// The exchange the symbol trades on - ie. 'NMS'
+   pub exchange_name: Option<String>,
+
+   /// The kind of instrument - ie. 'EQUITY' or 'ETF'
+   pub instrument_type: Option<String>,
+
+   /// When the symbol first traded
+   pub first_trade_date: DateTime<Utc>,
+
+   /// The latest regular market price
+   pub regular_market_price: f64,
+
+   /// The close before the start of the history
+   pub previous_close: f64,
+
+   /// The full timezone name of the exchange - ie. 'America/New_York'
+   pub exchange_timezone_name: Option<String>,
+
+   /// The offset of the exchange from UTC in seconds
+   pub gmt_offset: Option<i64>,
+
+   /// The number of decimal places prices are usually shown with
+   pub price_hint: Option<i64>,
+
+   /// The sessions for the current trading day
+   pub current_trading_period: Option<TradingPeriods>,
+
+   /// The ranges that Yahoo! can provide history for - ie. '1d', '5d', 'max'
+   pub valid_ranges: Vec<String>
+}
+impl ChartMeta {
+   fn new(data: &yahoo::Meta) -> ChartMeta {
+      ChartMeta {
+         symbol: data.symbol.clone(),
+         currency: data.currency.clone(),
+         exchange_name: data.exchange_name.clone(),
+         instrument_type: data.instrument_type.clone(),
+         first_trade_date: data.first_trade_date,
+         regular_market_price: data.current_price,
+         previous_close: data.previous_close,
+         exchange_timezone_name: data.exchange_timezone_name.clone(),
+         gmt_offset: data.gmtoffset,
+         price_hint: data.price_hint,
+         current_trading_period: data.current_trading_period.as_ref().map(|periods| TradingPeriods {
+            pre: TradingPeriod::new(&periods.pre),
+            regular: TradingPeriod::new(&periods.regular),
+            post: TradingPeriod::new(&periods.post)
+         }),
+         valid_ranges: data.valid_ranges.clone()
+      }
+   }
+}
+
+/// A dividend paid out by a symbol
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dividend {
+   /// The ex-dividend date in millisecond accuracy
+   pub timestamp: i64,
+
+   /// The amount paid per share
+   pub amount: f64
+}
+impl Timestamped for Dividend {
+   /// Gets the timestamp in millisecond accuracy
+   fn timestamp_millis(&self) -> i64 { self.timestamp }
+}
+
+/// A stock split for a symbol - ie. a 4:1 split has a numerator of 4 and a denominator of 1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Split {
+   /// The date of the split in millisecond accuracy
+   pub timestamp: i64,
+
+   /// The number of shares after the split
+   pub numerator: f64,
+
+   /// The number of shares before the split
+   pub denominator: f64
+}
+impl Timestamped for Split {
+   /// Gets the timestamp in millisecond accuracy
+   fn timestamp_millis(&self) -> i64 { self.timestamp }
+}
+
+/// The corporate actions for a symbol over a period of time
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Events {
+   /// The dividends paid out - ordered by ex-dividend date
+   pub dividends: Vec<Dividend>,
+
+   /// The stock splits - ordered by date
+   pub splits: Vec<Split>
+}
+
+/// Called as each symbol of a batch finishes with the symbol, the number of symbols
+/// finished so far and the total number of symbols in the batch
+pub type BatchProgress<'a> = &'a (dyn Fn(&str, usize, usize) + Sync);
+
+/// A window of a chunked retrieval that could not be loaded
+#[derive(Debug)]
+pub struct ChunkFailure {
+   /// The start of the window that failed
+   pub start: DateTime<Utc>,
+
+   /// The end of the window that failed
+   pub end: DateTime<Utc>,
+
+   /// Why the window failed
+   pub error: Error
+}
+
+/// The bars retrieved by a chunked retrieval along with any windows that failed
+#[derive(Debug)]
+pub struct ChunkedBars {
+   /// All of the bars that were loaded - sorted by timestamp and without duplicates
+   pub bars: Vec<Bar>,
+
+   /// The windows which could not be loaded
+   pub failures: Vec<ChunkFailure>
+}
+
+/// The limits Yahoo! places on intraday data as `(window, lookback)` in days - ie. the longest
+/// span that can be requested in a single call and how far back in time the data is kept.
+fn intraday_limits(granularity: Interval) -> (i64, i64) {
+   match granularity {
+      Interval::_1m => (7, 30),
+      Interval::_60m => (730, 730),
+
+      // everything else - including 90m, which isn't kept as long as 60m - only goes back 60 days
+      Interval::_2m | Interval::_5m | Interval::_15m | Interval::_30m | Interval::_90m => (60, 60),
+
+      // not intraday - which is rejected before we get here
+      _ => (60, 60)
+   }
+}
+
+/// The (rough) number of days covered by a non-intraday period
+fn period_days(period: Interval) -> i64 {
+   match period {
+      Interval::_1d => 1,
+      Interval::_5d => 5,
+      Interval::_1mo => 31,
+      Interval::_3mo => 92,
+      Interval::_6mo => 183,
+      Interval::_1y | Interval::_ytd => 366,
+      Interval::_2y => 731,
+      Interval::_5y => 1827,
+      Interval::_10y => 3653,
+      _ => i64::MAX
+   }
+}
+
+/// Makes sure that Yahoo! will give us intraday data of the given granularity between two dates
+fn check_intraday_range(granularity: Interval, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<()> {
+   ensure!(granularity.is_intraday(), error::NotIntraday { interval: granularity });
+   ensure!(end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+   let (_, lookback) = intraday_limits(granularity);
+   ensure!(Utc::now().signed_duration_since(start) <= Duration::days(lookback), error::IntradayTooOld { interval: granularity, days: lookback });
+   Ok(())
+}
+
+fn aggregate(data: yahoo::Data) -> Result<Vec<AdjustedBar>> {
    let mut result = Vec::new();
 
    let timestamps = &data.timestamps;
@@ -24,6 +271,12 @@ fn aggregate_bars(data: yahoo::Data) -> Result<Vec<Bar>> {
    ensure!(timestamps.len() == quote.lows.len(), error::MissingData { reason: "'low' values do not line up the timestamps" });
    ensure!(timestamps.len() == quote.closes.len(), error::MissingData { reason: "'close' values do not line up the timestamps" });
 
+   // adjusted closes are optional - Yahoo! does not send them for intraday data
+   let adjcloses = data.indicators.adjcloses.first().map(|adjclose| &adjclose.adjcloses);
+   if let Some(adjcloses) = adjcloses {
+      ensure!(timestamps.len() == adjcloses.len(), error::MissingData { reason: "'adjclose' values do not line up the timestamps" });
+   }
+
    #[allow(clippy::needless_range_loop)]
    for i in 0..timestamps.len() {
       // skip days where we have incomplete data
@@ -31,18 +284,223 @@ fn aggregate_bars(data: yahoo::Data) -> Result<Vec<Bar>> {
          continue;
       }
 
-      result.push(Bar {
-         timestamp: timestamps[i] * 1000,
-         open: quote.opens[i].context(error::InternalLogic{ reason: "missing open not caught" })?,
-         high: quote.highs[i].context(error::InternalLogic{ reason: "missing high not caught" })?,
-         low: quote.lows[i].context(error::InternalLogic{ reason: "missing low not caught" })?,
-         close: quote.closes[i].context(error::InternalLogic{ reason: "missing close not caught" })?,
-         volume: quote.volumes[i],
+      result.push(AdjustedBar {
+         bar: Bar {
+            timestamp: timestamps[i] * 1000,
+            open: quote.opens[i].context(error::InternalLogic{ reason: "missing open not caught" })?,
+            high: quote.highs[i].context(error::InternalLogic{ reason: "missing high not caught" })?,
+            low: quote.lows[i].context(error::InternalLogic{ reason: "missing low not caught" })?,
+            close: quote.closes[i].context(error::InternalLogic{ reason: "missing close not caught" })?,
+            volume: quote.volumes[i],
+         },
+         adjclose: adjcloses.and_then(|adjcloses| adjcloses[i])
       })
    }
    Ok(result)
 }
 
+fn aggregate_events(events: Option<&yahoo::Events>) -> Events {
+   let mut result = Events::default();
+
+   // no events section simply means nothing happened
+   let events = match events { Some(events) => events, None => return result };
+
+   result.dividends = events.dividends.values()
+      .map(|dividend| Dividend { timestamp: dividend.date * 1000, amount: dividend.amount })
+      .collect();
+   result.dividends.sort_by_key(|dividend| dividend.timestamp);
+
+   result.splits = events.splits.values()
+      .map(|split| Split { timestamp: split.date * 1000, numerator: split.numerator, denominator: split.denominator })
+      .collect();
+   result.splits.sort_by_key(|split| split.timestamp);
+
+   result
+}
+
+fn aggregate_bars(data: yahoo::Data) -> Result<Vec<Bar>> {
+   Ok(aggregate(data)?.into_iter().map(|adjusted| adjusted.bar).collect())
+}
+
+/// Historical quotes retrieved through a [`Client`](../struct.Client.html)
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ Client, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let client = Client::new().unwrap();
+///    let data = client.history().retrieve_interval("AAPL", Interval::_5d).await.unwrap();
+///    println!("Loaded {} bars", data.len());
+/// }
+/// ```
+pub struct History<'a> {
+   client: &'a Client
+}
+impl<'a> History<'a> {
+   pub(crate) fn new(client: &'a Client) -> History<'a> { History { client } }
+
+   /// Same as [`retrieve`](fn.retrieve.html) but using the client's configuration
+   pub async fn retrieve(&self, symbol: &str) -> Result<Vec<Bar>> {
+      aggregate_bars(yahoo::load_daily(self.client, symbol, Interval::_6mo).await?)
+   }
+
+   /// Same as [`retrieve_interval`](fn.retrieve_interval.html) but using the client's configuration
+   pub async fn retrieve_interval(&self, symbol: &str, interval: Interval) -> Result<Vec<Bar>> {
+      // pre-conditions
+      ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+      aggregate_bars(yahoo::load_daily(self.client, symbol, interval).await?)
+   }
+
+   /// Same as [`retrieve_range`](fn.retrieve_range.html) but using the client's configuration
+   pub async fn retrieve_range(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<Bar>> {
+      // pre-conditions
+      let _end = end.unwrap_or_else(Utc::now);
+      ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+      aggregate_bars(yahoo::load_daily_range(self.client, symbol, start.timestamp(), _end.timestamp()).await?)
+   }
+
+   /// Same as [`retrieve_intraday`](fn.retrieve_intraday.html) but using the client's configuration
+   pub async fn retrieve_intraday(&self, symbol: &str, granularity: Interval, period: Interval) -> Result<Vec<Bar>> {
+      // pre-conditions
+      ensure!(granularity.is_intraday(), error::NotIntraday { interval: granularity });
+      ensure!(!period.is_intraday(), error::NoIntraday { interval: period });
+
+      let (window, _) = intraday_limits(granularity);
+      ensure!(period_days(period) <= window, error::IntradayTooLong { interval: granularity, days: window });
+
+      aggregate_bars(yahoo::load_period(self.client, symbol, granularity, period).await?)
+   }
+
+   /// Same as [`retrieve_intraday_range`](fn.retrieve_intraday_range.html) but using the client's configuration
+   pub async fn retrieve_intraday_range(&self, symbol: &str, granularity: Interval, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<Bar>> {
+      // pre-conditions
+      let _end = end.unwrap_or_else(Utc::now);
+      check_intraday_range(granularity, start, _end)?;
+
+      let (window, _) = intraday_limits(granularity);
+      ensure!(_end.signed_duration_since(start) <= Duration::days(window), error::IntradayTooLong { interval: granularity, days: window });
+
+      aggregate_bars(yahoo::load_range(self.client, symbol, granularity, start.timestamp(), _end.timestamp()).await?)
+   }
+
+   /// Same as [`retrieve_intraday_chunked`](fn.retrieve_intraday_chunked.html) but using the client's configuration
+   pub async fn retrieve_intraday_chunked(&self, symbol: &str, granularity: Interval, start: DateTime<Utc>, end: Option<DateTime<Utc>>, concurrency: usize) -> Result<ChunkedBars> {
+      // pre-conditions
+      let _end = end.unwrap_or_else(Utc::now);
+      check_intraday_range(granularity, start, _end)?;
+
+      // split the range up into windows that Yahoo! will accept
+      let (window, _) = intraday_limits(granularity);
+      let mut windows = Vec::new();
+      let mut from = start;
+      while from < _end {
+         let to = std::cmp::min(from + Duration::days(window), _end);
+         windows.push((from, to));
+         from = to;
+      }
+
+      let results = stream::iter(windows)
+         .map(|(from, to)| async move {
+            let bars = match yahoo::load_range(self.client, symbol, granularity, from.timestamp(), to.timestamp()).await {
+               Ok(data) => aggregate_bars(data),
+               Err(e) => Err(e)
+            };
+            (from, to, bars)
+         })
+         .buffer_unordered(std::cmp::max(concurrency, 1))
+         .collect::<Vec<_>>()
+         .await;
+
+      // stitch the windows back together - windows can overlap so use the timestamp to remove duplicates
+      let mut bars = BTreeMap::new();
+      let mut failures = Vec::new();
+      for (from, to, result) in results {
+         match result {
+            Ok(chunk) => for bar in chunk { bars.insert(bar.timestamp, bar); },
+            Err(error) => failures.push(ChunkFailure { start: from, end: to, error })
+         }
+      }
+      failures.sort_by_key(|failure| failure.start);
+
+      Ok(ChunkedBars { bars: bars.into_values().collect(), failures })
+   }
+
+   /// Same as [`retrieve_adjusted`](fn.retrieve_adjusted.html) but using the client's configuration
+   pub async fn retrieve_adjusted(&self, symbol: &str, interval: Interval) -> Result<Vec<AdjustedBar>> {
+      // pre-conditions
+      ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+      aggregate(yahoo::load_daily(self.client, symbol, interval).await?)
+   }
+
+   /// Same as [`retrieve_range_adjusted`](fn.retrieve_range_adjusted.html) but using the client's configuration
+   pub async fn retrieve_range_adjusted(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<AdjustedBar>> {
+      // pre-conditions
+      let _end = end.unwrap_or_else(Utc::now);
+      ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+      aggregate(yahoo::load_daily_range(self.client, symbol, start.timestamp(), _end.timestamp()).await?)
+   }
+
+   /// Same as [`retrieve_events`](fn.retrieve_events.html) but using the client's configuration
+   pub async fn retrieve_events(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Events> {
+      // pre-conditions
+      let _end = end.unwrap_or_else(Utc::now);
+      ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+      let events = yahoo::load_daily_range_events(self.client, symbol, start.timestamp(), _end.timestamp()).await?;
+      Ok(aggregate_events(events.as_ref()))
+   }
+
+   /// Same as [`retrieve_range_with_events`](fn.retrieve_range_with_events.html) but using the client's configuration
+   pub async fn retrieve_range_with_events(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<(Vec<Bar>, Events)> {
+      // pre-conditions
+      let _end = end.unwrap_or_else(Utc::now);
+      ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+      let data = yahoo::load_daily_range_with_events(self.client, symbol, start.timestamp(), _end.timestamp()).await?;
+      let events = aggregate_events(data.events.as_ref());
+      Ok((aggregate_bars(data)?, events))
+   }
+
+   /// Same as [`retrieve_with_meta`](fn.retrieve_with_meta.html) but using the client's configuration
+   pub async fn retrieve_with_meta(&self, symbol: &str, interval: Interval) -> Result<(ChartMeta, Vec<Bar>)> {
+      // pre-conditions
+      ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+      let data = yahoo::load_daily(self.client, symbol, interval).await?;
+      let meta = ChartMeta::new(&data.meta);
+      Ok((meta, aggregate_bars(data)?))
+   }
+
+   /// Same as [`retrieve_batch`](fn.retrieve_batch.html) but using the client's configuration
+   pub async fn retrieve_batch(&self, symbols: &[&str], start: DateTime<Utc>, end: Option<DateTime<Utc>>, concurrency: usize, progress: Option<BatchProgress<'_>>) -> HashMap<String, Result<Vec<Bar>>> {
+      // a symbol asked for twice is only loaded - and counted - once
+      let mut unique: Vec<&str> = Vec::with_capacity(symbols.len());
+      for symbol in symbols {
+         if !unique.contains(symbol) { unique.push(symbol); }
+      }
+
+      let total = unique.len();
+      let mut results = HashMap::new();
+
+      let mut loads = stream::iter(unique)
+         .map(|symbol| async move { (symbol, self.retrieve_range(symbol, start, end).await) })
+         .buffer_unordered(std::cmp::max(concurrency, 1));
+
+      while let Some((symbol, result)) = loads.next().await {
+         results.insert(symbol.to_string(), result);
+         if let Some(progress) = progress { progress(symbol, results.len(), total); }
+      }
+      results
+   }
+}
+
 /// Retrieves (at most) 6 months worth of OCLHV data for a symbol
 /// ending on the last market close.
 ///
@@ -65,7 +523,7 @@ fn aggregate_bars(data: yahoo::Data) -> Result<Vec<Bar>> {
 /// }
 /// ```
 pub async fn retrieve(symbol: &str) -> Result<Vec<Bar>> {
-   aggregate_bars(yahoo::load_daily(symbol, Interval::_6mo).await?)
+   client::shared()?.history().retrieve(symbol).await
 }
 
 /// Retrieves a configurable amount of OCLHV data for a symbol
@@ -92,10 +550,7 @@ pub async fn retrieve(symbol: &str) -> Result<Vec<Bar>> {
 /// }
 /// ```
 pub async fn retrieve_interval(symbol: &str, interval: Interval) -> Result<Vec<Bar>> {
-   // pre-conditions
-   ensure!(!interval.is_intraday(), error::NoIntraday { interval });
-
-   aggregate_bars(yahoo::load_daily(symbol, interval).await?)
+   client::shared()?.history().retrieve_interval(symbol, interval).await
 }
 
 /// Retrieves OCLHV data for a symbol between a start and end date.
@@ -121,9 +576,240 @@ pub async fn retrieve_interval(symbol: &str, interval: Interval) -> Result<Vec<B
 /// }
 /// ```
 pub async fn retrieve_range(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<Bar>> {
-   // pre-conditions
-   let _end = end.unwrap_or_else(Utc::now);
-   ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+   client::shared()?.history().retrieve_range(symbol, start, end).await
+}
+
+/// Retrieves intraday OCLHV data for a symbol over a period ending now.
+///
+/// Yahoo! limits how much intraday data it provides - 1 minute bars are only
+/// available 7 days at a time, 60 minute bars for up to 730 days and all other
+/// granularities for up to 60 days.
+///
+/// # Examples
+///
+/// Get the last day of 5 minute bars for Apple:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    match history::retrieve_intraday("AAPL", Interval::_5m, Interval::_1d).await {
+///       Err(e) => println!("Failed to call Yahoo: {:?}", e),
+///       Ok(data) =>
+///          for bar in &data {
+///             println!("At {} Apple traded at ${:.2}", bar.datetime().format("%H:%M"), bar.close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve_intraday(symbol: &str, granularity: Interval, period: Interval) -> Result<Vec<Bar>> {
+   client::shared()?.history().retrieve_intraday(symbol, granularity, period).await
+}
+
+/// Retrieves intraday OCLHV data for a symbol between a start and end date.
+///
+/// The same limits as [`retrieve_intraday`](fn.retrieve_intraday.html) apply
+/// and the start date must be within Yahoo!'s lookback for the granularity -
+/// 30 days for 1 minute bars.
+///
+/// # Examples
+///
+/// Get 2 days worth of 1 minute Apple bars:
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::{ history, Interval, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let now = Utc::now();
+///    match history::retrieve_intraday_range("AAPL", Interval::_1m, now - Duration::days(2), None).await {
+///       Err(e) => println!("Failed to call Yahoo {:?}", e),
+///       Ok(data) =>
+///          for bar in &data {
+///             println!("At {} Apple traded at ${:.2}", bar.datetime().format("%b %e %H:%M"), bar.close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve_intraday_range(symbol: &str, granularity: Interval, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<Bar>> {
+   client::shared()?.history().retrieve_intraday_range(symbol, granularity, start, end).await
+}
+
+/// Retrieves intraday OCLHV data for a symbol between a start and end date,
+/// splitting the range into as many calls as Yahoo! needs.
+///
+/// Unlike [`retrieve_intraday_range`](fn.retrieve_intraday_range.html) the range
+/// can be longer than Yahoo! allows for a single call - ie. a month of 1 minute
+/// bars.  At most `concurrency` calls are made at the same time.  The bars from all
+/// of the calls are stitched together and any windows that fail are reported
+/// rather than failing the entire retrieval.
+///
+/// # Examples
+///
+/// Get the last 3 weeks worth of 1 minute Apple bars:
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::{ history, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let start = Utc::now() - Duration::days(21);
+///    let result = history::retrieve_intraday_chunked("AAPL", Interval::_1m, start, None, 2).await.unwrap();
+///
+///    println!("Loaded {} bars", result.bars.len());
+///    for failure in &result.failures {
+///       println!("Could not load {} to {} - {}", failure.start, failure.end, failure.error);
+///    }
+/// }
+/// ```
+pub async fn retrieve_intraday_chunked(symbol: &str, granularity: Interval, start: DateTime<Utc>, end: Option<DateTime<Utc>>, concurrency: usize) -> Result<ChunkedBars> {
+   client::shared()?.history().retrieve_intraday_chunked(symbol, granularity, start, end, concurrency).await
+}
 
-   aggregate_bars(yahoo::load_daily_range(symbol, start.timestamp(), _end.timestamp()).await?)
+/// Retrieves a configurable amount of OCLHV data for a symbol along with
+/// the split & dividend adjusted close for each bar.
+///
+/// Use [`AdjustedBar::adjusted`](struct.AdjustedBar.html#method.adjusted) to get
+/// bars where the entire OHLC is adjusted - ie. for backtesting across
+/// corporate actions.
+///
+/// # Examples
+///
+/// Get a year of fully adjusted Apple data:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let data = history::retrieve_adjusted("AAPL", Interval::_1y).await.unwrap();
+///    for bar in data.iter().map(|bar| bar.adjusted()) {
+///       println!("On {} Apple closed at ${:.2} (adjusted)", bar.datetime().format("%b %e %Y"), bar.close)
+///    }
+/// }
+/// ```
+pub async fn retrieve_adjusted(symbol: &str, interval: Interval) -> Result<Vec<AdjustedBar>> {
+   client::shared()?.history().retrieve_adjusted(symbol, interval).await
+}
+
+/// Retrieves OCLHV data for a symbol between a start and end date along with
+/// the split & dividend adjusted close for each bar.
+///
+/// # Examples
+///
+/// Get the adjusted closes for Apple over the last 30 days:
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::{ history, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let data = history::retrieve_range_adjusted("AAPL", Utc::now() - Duration::days(30), None).await.unwrap();
+///    for bar in &data {
+///       println!("On {} Apple closed at ${:.2} - adjusted {:?}", bar.datetime().format("%b %e %Y"), bar.bar.close, bar.adjclose)
+///    }
+/// }
+/// ```
+pub async fn retrieve_range_adjusted(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<AdjustedBar>> {
+   client::shared()?.history().retrieve_range_adjusted(symbol, start, end).await
+}
+
+/// Retrieves the dividends and stock splits for a symbol between a start and end date.
+///
+/// # Examples
+///
+/// Get the last 5 years of Apple dividends:
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::{ history, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let events = history::retrieve_events("AAPL", Utc::now() - Duration::days(5 * 365), None).await.unwrap();
+///    for dividend in &events.dividends {
+///       println!("On {} Apple paid ${:.2}", dividend.datetime().format("%b %e %Y"), dividend.amount)
+///    }
+/// }
+/// ```
+pub async fn retrieve_events(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Events> {
+   client::shared()?.history().retrieve_events(symbol, start, end).await
+}
+
+/// Retrieves OCLHV data for a symbol between a start and end date along with
+/// the dividends and stock splits over the same period.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::history;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (bars, events) = history::retrieve_range_with_events("AAPL", Utc::now() - Duration::days(365), None).await.unwrap();
+///    println!("{} bars, {} dividends & {} splits", bars.len(), events.dividends.len(), events.splits.len());
+/// }
+/// ```
+pub async fn retrieve_range_with_events(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<(Vec<Bar>, Events)> {
+   client::shared()?.history().retrieve_range_with_events(symbol, start, end).await
+}
+
+/// Retrieves a configurable amount of OCLHV data for a symbol along with
+/// the information Yahoo! has about the symbol - ie. its currency and timezone.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (meta, bars) = history::retrieve_with_meta("AAPL", Interval::_1mo).await.unwrap();
+///    println!("{} bars in {:?} from {:?}", bars.len(), meta.currency, meta.exchange_timezone_name);
+/// }
+/// ```
+pub async fn retrieve_with_meta(symbol: &str, interval: Interval) -> Result<(ChartMeta, Vec<Bar>)> {
+   client::shared()?.history().retrieve_with_meta(symbol, interval).await
+}
+
+/// Retrieves OCLHV data for many symbols between a start and end date.
+///
+/// At most `concurrency` symbols are loaded at the same time and each symbol
+/// gets its own result - so one bad symbol doesn't stop the others from
+/// loading.  If given, `progress` is called as each symbol finishes with the
+/// symbol, the number of symbols finished so far and the total number of symbols.
+/// A symbol that's given more than once is only loaded once.  The batch itself
+/// only fails if the shared client can't be created.
+///
+/// Large batches are best loaded through a [`Client`](../struct.Client.html) with a
+/// rate limit, so that Yahoo! doesn't start turning the calls away.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::{Client, RateLimiter};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let client = Client::builder().rate_limit(RateLimiter::new(2.0, 5)).build().unwrap();
+///    let start = Utc::now() - Duration::days(30);
+///    let progress = |symbol: &str, done: usize, total: usize| println!("{} loaded ({}/{})", symbol, done, total);
+///
+///    let results = client.history().retrieve_batch(&["AAPL", "MSFT", "FUBAR"], start, None, 4, Some(&progress)).await;
+///    for (symbol, result) in &results {
+///       match result {
+///          Ok(bars) => println!("{} has {} bars", symbol, bars.len()),
+///          Err(e) => println!("{} failed - {}", symbol, e)
+///       }
+///    }
+/// }
+/// ```
+pub async fn retrieve_batch(symbols: &[&str], start: DateTime<Utc>, end: Option<DateTime<Utc>>, concurrency: usize, progress: Option<BatchProgress<'_>>) -> Result<HashMap<String, Result<Vec<Bar>>>> {
+   Ok(client::shared()?.history().retrieve_batch(symbols, start, end, concurrency, progress).await)
 }
@@ -4,8 +4,11 @@
 //! that information out of Yahoo for use in financial applications.
 //!
 //! Currently `yahoo_finance` provides:
-//! * Historical quote information [OHCL Data](https://en.wikipedia.org/wiki/Open-high-low-close_chart) + volume
+//! * Historical quote information [OHCL Data](https://en.wikipedia.org/wiki/Open-high-low-close_chart) + volume, daily or intraday
 //! * Relatively real-time quote informaton with comparible performance to the real-time updates on their website
+//! * Snapshot quotes for many symbols at once - price, bid / ask, day & 52 week ranges, etc.
+//! * Option chains - expiration dates along with the calls & puts for an expiration
+//! * Symbol search - ie. finding `AAPL` from "apple"
 //! * Company profile information including address, sector, industry, etc.
 //! 
 //! ## Quick Examples
@@ -37,15 +40,35 @@
 //! async fn main() {
 //!    let streamer = Streamer::new(vec!["AAPL", "QQQ", "^DJI", "^IXIC"]);
 //!
-//!    streamer.stream().await
+//!    streamer.stream().await.unwrap()
 //!       .for_each(|quote| {
-//!          println!("At {}, {} is trading for ${}", quote.timestamp, quote.symbol, quote.price);
+//!          match quote {
+//!             Ok(quote) => println!("At {}, {} is trading for ${}", quote.timestamp, quote.symbol, quote.price),
+//!             Err(e) => println!("Problem reading a quote - {}", e)
+//!          }
 //!          future::ready(())
 //!       })
 //!       .await;
 //! }
 //! ```
 //!
+//! To control how Yahoo! is called - ie. timeouts, proxies or a different endpoint -
+//! use a `Client`.  All of its calls share one connection pool, retry policy and
+//! rate limit.  The free functions only share them too once the client is installed
+//! with `Client::set_default`.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use yahoo_finance::{Client, Interval};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!    let client = Client::builder().timeout(Duration::from_secs(5)).build().unwrap();
+//!    let data = client.history().retrieve_interval("AAPL", Interval::_1mo).await.unwrap();
+//!    println!("Apple closed at ${:.2}", data.last().unwrap().close);
+//! }
+//! ```
+//!
 //! To get the industry & sectory for a symbol.
 //!
 //! ```no_run
@@ -71,18 +94,71 @@ use snafu::Snafu;
 
 #[derive(Debug, Snafu)]
 pub struct Error(error::InnerError);
+impl Error {
+   /// The number of times the failed call was attempted
+   pub fn attempts(&self) -> u32 {
+      match &self.0 {
+         error::InnerError::RetriesExhausted { attempts, .. } => *attempts,
+         _ => 1
+      }
+   }
+
+   /// Determines if the failure is transient - ie. Yahoo! throttled us or had a server
+   /// error - so the call is worth trying again later, or fatal.  HTTP statuses are
+   /// judged by the retry policy of the client that made the call.
+   pub fn is_retryable(&self) -> bool {
+      match &self.0 {
+         error::InnerError::RateLimited => true,
+         error => error.is_retryable()
+      }
+   }
+}
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 mod yahoo;
 
+/// Configurable access to Yahoo!
+mod client;
+pub use client::{Client, ClientBuilder};
+pub use reqwest::{header::HeaderMap, Proxy};
+
+/// Limiting how quickly Yahoo! is called
+mod rate_limit;
+pub use rate_limit::{RateLimitMode, RateLimiter};
+
+/// Retrying transient failures
+mod retry;
+pub use retry::RetryPolicy;
+
 /// Historical quotes
 pub mod history;
 
+/// Option chains
+pub mod options;
+
+/// Symbol search
+pub mod search;
+
 /// Realtime quotes
 mod streaming;
-pub use streaming::Streamer;
+pub use streaming::{
+   BarAggregator, Conflate, Conflator, ConnectionState, CryptoDetails, HubStream, LagPolicy, LiveBar, OptionDetails, QuoteHub, ReconnectPolicy,
+   Recorder, Replay, ReplaySpeed, Session, SessionChange, SessionTracker, StreamHandle, StreamStats, Streamer, Tick, TypedTick
+};
+
+/// The kinds of securities
+mod quote_type;
+pub use quote_type::QuoteType;
 
 /// Symbol profile
 mod profile;
-pub use profile::Profile;
\ No newline at end of file
+pub use profile::Profile;
+
+/// Snapshot quotes
+mod snapshot;
+pub use snapshot::Snapshot;
+
+/// Testing streamers without the network
+#[cfg(feature = "test-support")]
+pub mod test_support;
\ No newline at end of file
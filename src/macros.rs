@@ -1,6 +1,7 @@
 /// Used in conjunction with Serde to create good public structures
 macro_rules! ez_serde {
    ($name:ident$(< $( $lt:lifetime ),+ >)? { $($(#[$m:meta])? $field:ident: $t:ty),* } ) => {
+      #[allow(dead_code)]
       #[derive(Clone, Deserialize)]
       #[serde(rename_all(deserialize = "camelCase"))]
       pub struct $name$(< $($lt),* >)? {
@@ -8,6 +9,7 @@ macro_rules! ez_serde {
       }
    };
    ($name:ident { $($(#[$m:meta])? $field:ident: $t:ty),* } ) => {
+      #[allow(dead_code)]
       #[derive(Clone, Deserialize)]
       #[serde(rename_all(deserialize = "camelCase"))]
       pub struct $name {
@@ -0,0 +1,183 @@
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::{client, yahoo, Client, Result};
+
+/// Whether an option is the right to buy or to sell
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OptionKind {
+   /// The right to buy the underlying at the strike price
+   Call,
+
+   /// The right to sell the underlying at the strike price
+   Put
+}
+
+/// A single option contract
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionContract {
+   /// The symbol for the contract itself - ie. 'AAPL200508C00150000'
+   pub contract_symbol: String,
+
+   /// Whether the contract is a call or a put
+   pub kind: OptionKind,
+
+   /// The price the underlying can be bought or sold at
+   pub strike: f64,
+
+   /// When the contract expires
+   pub expiration: DateTime<Utc>,
+
+   /// The currency that prices are in - ie. 'USD'
+   pub currency: Option<String>,
+
+   /// The price of the last trade
+   pub last_price: Option<f64>,
+
+   /// When the last trade happened
+   pub last_trade: Option<DateTime<Utc>>,
+
+   /// The change in price since the previous close
+   pub change: Option<f64>,
+
+   /// The change in price since the previous close as a percentage
+   pub percent_change: Option<f64>,
+
+   /// The best bid
+   pub bid: Option<f64>,
+
+   /// The best ask
+   pub ask: Option<f64>,
+
+   /// The number of contracts traded today
+   pub volume: Option<u64>,
+
+   /// The number of contracts that are currently open
+   pub open_interest: Option<u64>,
+
+   /// The implied volatility as a fraction - ie. 0.25 is 25%
+   pub implied_volatility: Option<f64>,
+
+   /// Whether the contract is currently in the money
+   pub in_the_money: bool
+}
+impl OptionContract {
+   fn new(data: yahoo::Contract, kind: OptionKind) -> OptionContract {
+      OptionContract {
+         contract_symbol: data.contract_symbol,
+         kind,
+         strike: data.strike,
+         expiration: data.expiration,
+         currency: data.currency,
+         last_price: data.last_price,
+         last_trade: data.last_trade_date.and_then(|time| Utc.timestamp_opt(time, 0).single()),
+         change: data.change,
+         percent_change: data.percent_change,
+         bid: data.bid,
+         ask: data.ask,
+         volume: data.volume,
+         open_interest: data.open_interest,
+         implied_volatility: data.implied_volatility,
+         in_the_money: data.in_the_money
+      }
+   }
+}
+
+/// The calls and puts for an underlying symbol that share an expiration date
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionChain {
+   /// The symbol the options are for
+   pub underlying: String,
+
+   /// When the options expire
+   pub expiration: DateTime<Utc>,
+
+   /// The calls - ordered by strike
+   pub calls: Vec<OptionContract>,
+
+   /// The puts - ordered by strike
+   pub puts: Vec<OptionContract>
+}
+
+/// Option chains retrieved through a [`Client`](../struct.Client.html)
+pub struct Options<'a> {
+   client: &'a Client
+}
+impl<'a> Options<'a> {
+   pub(crate) fn new(client: &'a Client) -> Options<'a> { Options { client } }
+
+   /// Same as [`expirations`](fn.expirations.html) but using the client's configuration
+   pub async fn expirations(&self, symbol: &str) -> Result<Vec<DateTime<Utc>>> {
+      let result = match yahoo::load_options(self.client, symbol, None).await? {
+         Some(result) => result,
+         None => return Ok(Vec::new())
+      };
+
+      Ok(result.expiration_dates.iter().filter_map(|date| Utc.timestamp_opt(*date, 0).single()).collect())
+   }
+
+   /// Same as [`chain`](fn.chain.html) but using the client's configuration
+   pub async fn chain(&self, symbol: &str, expiration: DateTime<Utc>) -> Result<OptionChain> {
+      // Yahoo! expirations are at midnight UTC - so ask for the start of the day
+      let start = expiration.timestamp() - expiration.timestamp().rem_euclid(86_400);
+      let expiration = Utc.timestamp_opt(start, 0).single().unwrap_or(expiration);
+
+      let mut chain = OptionChain { underlying: symbol.to_string(), expiration, calls: Vec::new(), puts: Vec::new() };
+
+      let result = match yahoo::load_options(self.client, symbol, Some(expiration.timestamp())).await? {
+         Some(result) => result,
+         None => return Ok(chain)
+      };
+      chain.underlying = result.underlying_symbol;
+
+      for options in result.options.into_iter().filter(|options| options.expiration_date.date_naive() == expiration.date_naive()) {
+         chain.calls.extend(options.calls.into_iter().map(|contract| OptionContract::new(contract, OptionKind::Call)));
+         chain.puts.extend(options.puts.into_iter().map(|contract| OptionContract::new(contract, OptionKind::Put)));
+      }
+      chain.calls.sort_by(|a, b| a.strike.partial_cmp(&b.strike).unwrap_or(std::cmp::Ordering::Equal));
+      chain.puts.sort_by(|a, b| a.strike.partial_cmp(&b.strike).unwrap_or(std::cmp::Ordering::Equal));
+
+      Ok(chain)
+   }
+}
+
+/// Retrieves the expiration dates that a symbol has options for.  There are
+/// no expiration dates if the symbol doesn't have options.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::options;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for expiration in options::expirations("AAPL").await.unwrap() {
+///       println!("Apple has options expiring on {}", expiration.format("%b %e %Y"));
+///    }
+/// }
+/// ```
+pub async fn expirations(symbol: &str) -> Result<Vec<DateTime<Utc>>> {
+   client::shared()?.options().expirations(symbol).await
+}
+
+/// Retrieves the calls and puts for a symbol that expire on a given date - which
+/// should be one of the dates from [`expirations`](fn.expirations.html).  Only
+/// the (UTC) date matters, so any time on that day finds the same contracts.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::options;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let expiration = options::expirations("AAPL").await.unwrap()[0];
+///    let chain = options::chain("AAPL", expiration).await.unwrap();
+///
+///    for call in &chain.calls {
+///       println!("{} @ {} - bid {:?} / ask {:?}, IV {:?}", call.contract_symbol, call.strike, call.bid, call.ask, call.implied_volatility);
+///    }
+/// }
+/// ```
+pub async fn chain(symbol: &str, expiration: DateTime<Utc>) -> Result<OptionChain> {
+   client::shared()?.options().chain(symbol, expiration).await
+}
@@ -1,4 +1,4 @@
-use crate::{error, yahoo, Result};
+use crate::{client, error, yahoo, Client, Result};
 
 /// Symbols which represent a company can have an address associated with them.
 /// This is usually the company headquarters.
@@ -89,7 +89,11 @@ pub enum Profile {
 }
 impl Profile {
    pub async fn load(symbol: &str) -> Result<Profile> {
-      let data = yahoo::scrape(symbol).await?.quote_summary_store;
+      client::shared()?.profile(symbol).await
+   }
+
+   pub(crate) async fn load_with(client: &Client, symbol: &str) -> Result<Profile> {
+      let data = yahoo::scrape(client, symbol).await?.quote_summary_store;
 
       let kind = &data.quote_type.kind;
       match kind.as_str() {
@@ -0,0 +1,62 @@
+/// The kind of security a symbol represents
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QuoteType {
+   /// Shares in a company - ie. 'AAPL'
+   Equity,
+
+   /// An exchange traded fund - ie. 'SPY'
+   Etf,
+
+   /// A market index - ie. '^GSPC'
+   Index,
+
+   /// A mutual fund - ie. 'VFIAX'
+   MutualFund,
+
+   /// A money market fund
+   MoneyMarket,
+
+   /// An option contract - ie. 'AAPL200918C00150000'
+   Option,
+
+   /// An exchange rate between two currencies - ie. 'EURUSD=X'
+   Currency,
+
+   /// A cryptocurrency - ie. 'BTC-USD'
+   Cryptocurrency,
+
+   /// A futures contract - ie. 'ES=F'
+   Future,
+
+   /// A warrant issued by a company
+   Warrant,
+
+   /// A bond
+   Bond,
+
+   /// A commodity
+   Commodity,
+
+   /// Anything else - ie. Yahoo! industry or indicator symbols
+   Other
+}
+impl QuoteType {
+   /// Converts the name Yahoo! uses for a kind of security - ie. 'EQUITY' or 'ETF'
+   pub(crate) fn from_name(name: &str) -> QuoteType {
+      match name {
+         "EQUITY" => Self::Equity,
+         "ETF" => Self::Etf,
+         "INDEX" => Self::Index,
+         "MUTUALFUND" => Self::MutualFund,
+         "MONEYMARKET" => Self::MoneyMarket,
+         "OPTION" => Self::Option,
+         "CURRENCY" => Self::Currency,
+         "CRYPTOCURRENCY" => Self::Cryptocurrency,
+         "FUTURE" => Self::Future,
+         "WARRANT" => Self::Warrant,
+         "BOND" => Self::Bond,
+         "COMMODITY" => Self::Commodity,
+         _ => Self::Other
+      }
+   }
+}
@@ -0,0 +1,132 @@
+use snafu::ensure;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{error, Result};
+
+/// What to do when a call is made and the rate limit has been reached
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RateLimitMode {
+   /// Wait until there is room to make the call
+   Wait,
+
+   /// Fail the call immediately with a `RateLimited` error
+   FailFast
+}
+
+#[derive(Debug)]
+struct Bucket {
+   tokens: f64,
+   updated: Instant
+}
+
+/// A token bucket that limits how quickly calls are made to Yahoo!
+///
+/// Up to `burst` calls can be made at once, after which calls are allowed at
+/// `requests_per_second`.  Cloning a limiter shares the bucket - so giving the
+/// same limiter to several clients limits all of them together.
+///
+/// A limiter only applies to the [`Client`](struct.Client.html) it's given to.  The
+/// free functions like [`history::retrieve`](history/fn.retrieve.html) share a
+/// default client that isn't rate limited - unless a limited client is installed
+/// with [`Client::set_default`](struct.Client.html#method.set_default) first.
+///
+/// # Examples
+///
+/// ```
+/// use yahoo_finance::{Client, RateLimiter};
+///
+/// // at most 2 calls a second with bursts of up to 5 calls
+/// let client = Client::builder().rate_limit(RateLimiter::new(2.0, 5)).build().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+   rate: f64,
+   burst: f64,
+   mode: RateLimitMode,
+   bucket: Arc<Mutex<Bucket>>
+}
+impl RateLimiter {
+   /// Creates a limiter that waits for room when the limit has been reached
+   ///
+   /// # Panics
+   ///
+   /// This panics if `requests_per_second` is not positive.
+   pub fn new(requests_per_second: f64, burst: u32) -> RateLimiter {
+      assert!(requests_per_second > 0.0, "requests_per_second must be positive");
+      let burst = f64::from(std::cmp::max(burst, 1));
+
+      RateLimiter {
+         rate: requests_per_second,
+         burst,
+         mode: RateLimitMode::Wait,
+         bucket: Arc::new(Mutex::new(Bucket { tokens: burst, updated: Instant::now() }))
+      }
+   }
+
+   /// Changes what happens when the limit has been reached
+   pub fn mode(mut self, mode: RateLimitMode) -> RateLimiter {
+      self.mode = mode;
+      self
+   }
+
+   /// Takes a slot for a call - either waiting for one to free up or failing
+   /// with a `RateLimited` error depending on the mode.
+   ///
+   /// If the returned future is dropped while it's waiting - ie. it timed out
+   /// or lost a `select!` - the slot it reserved is given back.
+   pub async fn acquire(&self) -> Result<()> {
+      let (reservation, wait) = {
+         let mut bucket = self.bucket.lock().unwrap();
+         self.refill(&mut bucket);
+
+         // when failing fast we never go into debt
+         ensure!(self.mode == RateLimitMode::Wait || bucket.tokens >= 1.0, error::RateLimited);
+
+         // reserve our slot now so that waiting callers are served in order
+         bucket.tokens -= 1.0;
+         let wait = if bucket.tokens >= 0.0 { None } else { Some(Duration::from_secs_f64(-bucket.tokens / self.rate)) };
+         (Reservation { limiter: self, used: false }, wait)
+      };
+
+      if let Some(wait) = wait { tokio::time::delay_for(wait).await; }
+      reservation.keep();
+      Ok(())
+   }
+
+   /// Takes a slot for a call if one is available right now
+   pub fn try_acquire(&self) -> bool {
+      let mut bucket = self.bucket.lock().unwrap();
+      self.refill(&mut bucket);
+
+      if bucket.tokens < 1.0 { return false; }
+      bucket.tokens -= 1.0;
+      true
+   }
+
+   /// Adds the tokens earned since the bucket was last updated
+   fn refill(&self, bucket: &mut Bucket) {
+      let now = Instant::now();
+      let earned = now.duration_since(bucket.updated).as_secs_f64() * self.rate;
+      bucket.tokens = (bucket.tokens + earned).min(self.burst);
+      bucket.updated = now;
+   }
+}
+
+/// A slot taken from the bucket that's given back unless the call goes ahead
+struct Reservation<'a> {
+   limiter: &'a RateLimiter,
+   used: bool
+}
+impl Reservation<'_> {
+   fn keep(mut self) { self.used = true; }
+}
+impl Drop for Reservation<'_> {
+   fn drop(&mut self) {
+      if self.used { return; }
+
+      let mut bucket = self.limiter.bucket.lock().unwrap();
+      self.limiter.refill(&mut bucket);
+      bucket.tokens = (bucket.tokens + 1.0).min(self.limiter.burst);
+   }
+}
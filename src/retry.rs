@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::Duration;
+
+/// Controls how calls to Yahoo! are retried when they fail for a transient
+/// reason - ie. Yahoo! is throttling us or has a burst of server errors.
+///
+/// The delay between attempts doubles (by default) with every attempt, up to
+/// `max_backoff`.  A `Retry-After` header sent with a retryable response takes
+/// precedence - but is never waited on for longer than `max_backoff` either.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use yahoo_finance::{Client, RetryPolicy};
+///
+/// let client = Client::builder()
+///    .retry(RetryPolicy { max_attempts: 5, initial_backoff: Duration::from_secs(1), ..RetryPolicy::default() })
+///    .build()
+///    .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+   /// The most times a call is attempted - including the first attempt
+   pub max_attempts: u32,
+
+   /// How long to wait before the first retry
+   pub initial_backoff: Duration,
+
+   /// The longest we'll wait between attempts
+   pub max_backoff: Duration,
+
+   /// How much the wait grows by after each attempt
+   pub multiplier: f64,
+
+   /// Whether to randomize the wait so that many clients don't retry in lock step
+   pub jitter: bool,
+
+   /// The HTTP status codes that are worth retrying
+   pub retryable_statuses: Vec<u16>
+}
+impl RetryPolicy {
+   /// A policy that never retries
+   pub fn none() -> RetryPolicy {
+      RetryPolicy { max_attempts: 1, ..RetryPolicy::default() }
+   }
+
+   /// The time to wait after a failed attempt (starting at 1) before trying again
+   pub(crate) fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+      if let Some(retry_after) = retry_after { return retry_after.min(self.max_backoff); }
+      exponential_backoff(self.initial_backoff, self.max_backoff, self.multiplier, self.jitter, attempt)
+   }
+}
+impl Default for RetryPolicy {
+   fn default() -> RetryPolicy {
+      RetryPolicy {
+         max_attempts: 3,
+         initial_backoff: Duration::from_millis(500),
+         max_backoff: Duration::from_secs(30),
+         multiplier: 2.0,
+         jitter: true,
+         retryable_statuses: vec![429, 500, 502, 503, 504]
+      }
+   }
+}
+
+/// The wait after a failed attempt (starting at 1) that grows by `multiplier`
+/// with every attempt, up to `max`
+pub(crate) fn exponential_backoff(initial: Duration, max: Duration, multiplier: f64, jitter: bool, attempt: u32) -> Duration {
+   let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+   let backoff = initial.as_secs_f64() * multiplier.powi(exponent);
+   let backoff = backoff.min(max.as_secs_f64());
+
+   // full jitter - pick anywhere between no wait and the full backoff
+   let backoff = if jitter { rand::thread_rng().gen_range(0.0, 1.0) * backoff } else { backoff };
+   Duration::from_secs_f64(backoff.max(0.0))
+}
+
+/// Reads the `Retry-After` header - which is either a number of seconds or an HTTP date
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+   let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+   if let Ok(seconds) = value.parse::<u64>() { return Some(Duration::from_secs(seconds)); }
+
+   let when = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+   when.signed_duration_since(Utc::now()).to_std().ok().or(Some(Duration::from_secs(0)))
+}
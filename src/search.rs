@@ -0,0 +1,112 @@
+use crate::{client, yahoo, Client, QuoteType, Result};
+
+/// The most matches we ask Yahoo! for
+const MAX_MATCHES: usize = 20;
+
+/// The most matches we ask Yahoo! for when only some kinds of security are wanted -
+/// since the ones we want can be well down the list
+const MAX_TYPED_MATCHES: usize = 100;
+
+/// A symbol that matches a search
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymbolMatch {
+   /// The symbol - ie. 'AAPL'
+   pub symbol: String,
+
+   /// The short name for the symbol - ie. 'Apple Inc.'
+   pub short_name: Option<String>,
+
+   /// The full name for the symbol
+   pub long_name: Option<String>,
+
+   /// The exchange the symbol trades on - ie. 'NMS'
+   pub exchange: Option<String>,
+
+   /// The display name of the exchange - ie. 'NASDAQ'
+   pub exchange_display: Option<String>,
+
+   /// The kind of security the symbol represents
+   pub quote_type: QuoteType,
+
+   /// How well the symbol matches the search according to Yahoo!
+   pub score: Option<f64>
+}
+impl SymbolMatch {
+   fn new(data: yahoo::Match) -> SymbolMatch {
+      SymbolMatch {
+         symbol: data.symbol,
+         short_name: data.short_name,
+         long_name: data.long_name,
+         exchange: data.exchange,
+         exchange_display: data.exchange_display,
+         quote_type: data.quote_type.as_deref().map_or(QuoteType::Other, QuoteType::from_name),
+         score: data.score
+      }
+   }
+}
+
+/// Symbol search through a [`Client`](../struct.Client.html)
+pub struct Search<'a> {
+   client: &'a Client
+}
+impl<'a> Search<'a> {
+   pub(crate) fn new(client: &'a Client) -> Search<'a> { Search { client } }
+
+   /// Same as [`symbols`](fn.symbols.html) but using the client's configuration
+   pub async fn symbols(&self, query: &str) -> Result<Vec<SymbolMatch>> {
+      Ok(yahoo::search(self.client, query, MAX_MATCHES).await?
+         .into_iter()
+         .map(SymbolMatch::new)
+         .collect())
+   }
+
+   /// Same as [`symbols_of_type`](fn.symbols_of_type.html) but using the client's configuration
+   pub async fn symbols_of_type(&self, query: &str, quote_type: QuoteType) -> Result<Vec<SymbolMatch>> {
+      Ok(yahoo::search(self.client, query, MAX_TYPED_MATCHES).await?
+         .into_iter()
+         .map(SymbolMatch::new)
+         .filter(|found| found.quote_type == quote_type)
+         .collect())
+   }
+}
+
+/// Searches for symbols by name or partial symbol - best matches first.  At
+/// most the best 20 matches are returned.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::search;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for found in search::symbols("apple").await.unwrap() {
+///       println!("{} - {:?} on {:?}", found.symbol, found.short_name, found.exchange);
+///    }
+/// }
+/// ```
+pub async fn symbols(query: &str) -> Result<Vec<SymbolMatch>> {
+   client::shared()?.search().symbols(query).await
+}
+
+/// Searches for symbols of a particular kind - ie. only equities.
+///
+/// The matches are filtered after Yahoo! has found them, so only the kind
+/// wanted among the best 100 matches of any kind are returned.
+///
+/// # Examples
+///
+/// Resolve "apple" to a ticker before loading its history:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, search, QuoteType };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let apple = &search::symbols_of_type("apple", QuoteType::Equity).await.unwrap()[0];
+///    let data = history::retrieve(&apple.symbol).await.unwrap();
+/// }
+/// ```
+pub async fn symbols_of_type(query: &str, quote_type: QuoteType) -> Result<Vec<SymbolMatch>> {
+   client::shared()?.search().symbols_of_type(query, quote_type).await
+}
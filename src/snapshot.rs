@@ -0,0 +1,137 @@
+use futures::future::try_join_all;
+
+use crate::{client, yahoo, Client, Result};
+
+/// The most symbols we ask Yahoo! for in a single call
+const MAX_SYMBOLS_PER_CALL: usize = 200;
+
+/// A point in time quote for a symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+   /// The symbol for the quote
+   pub symbol: String,
+
+   /// The short name for the symbol - ie. 'Apple Inc.'
+   pub short_name: Option<String>,
+
+   /// The full name for the symbol
+   pub long_name: Option<String>,
+
+   /// The kind of symbol - ie. 'EQUITY', 'ETF' or 'INDEX'
+   pub quote_type: Option<String>,
+
+   /// The currency that prices are in - ie. 'USD'
+   pub currency: Option<String>,
+
+   /// The exchange the symbol trades on - ie. 'NMS'
+   pub exchange: Option<String>,
+
+   /// The state of the market - ie. 'PRE', 'REGULAR', 'POST' or 'CLOSED'
+   pub market_state: Option<String>,
+
+   /// The time of the last regular market trade in millisecond accuracy
+   pub timestamp: Option<i64>,
+
+   /// The last regular market price
+   pub price: Option<f64>,
+
+   /// The change in price since the previous close
+   pub change: Option<f64>,
+
+   /// The change in price since the previous close as a percentage
+   pub change_percent: Option<f64>,
+
+   /// The price at the start of the day
+   pub open: Option<f64>,
+
+   /// The closing price of the previous day
+   pub previous_close: Option<f64>,
+
+   /// The highest price of the day
+   pub day_high: Option<f64>,
+
+   /// The lowest price of the day
+   pub day_low: Option<f64>,
+
+   /// The volume traded during the day
+   pub volume: Option<u64>,
+
+   /// The best bid
+   pub bid: Option<f64>,
+
+   /// The size of the best bid
+   pub bid_size: Option<u64>,
+
+   /// The best ask
+   pub ask: Option<f64>,
+
+   /// The size of the best ask
+   pub ask_size: Option<u64>,
+
+   /// The highest price over the last 52 weeks
+   pub fifty_two_week_high: Option<f64>,
+
+   /// The lowest price over the last 52 weeks
+   pub fifty_two_week_low: Option<f64>,
+
+   /// The market capitalization
+   pub market_cap: Option<u64>
+}
+impl Snapshot {
+   fn new(data: yahoo::QuoteResult) -> Snapshot {
+      Snapshot {
+         symbol: data.symbol,
+         short_name: data.short_name,
+         long_name: data.long_name,
+         quote_type: data.quote_type,
+         currency: data.currency,
+         exchange: data.exchange,
+         market_state: data.market_state,
+         timestamp: data.regular_market_time.map(|time| time * 1000),
+         price: data.regular_market_price,
+         change: data.regular_market_change,
+         change_percent: data.regular_market_change_percent,
+         open: data.regular_market_open,
+         previous_close: data.regular_market_previous_close,
+         day_high: data.regular_market_day_high,
+         day_low: data.regular_market_day_low,
+         volume: data.regular_market_volume,
+         bid: data.bid,
+         bid_size: data.bid_size,
+         ask: data.ask,
+         ask_size: data.ask_size,
+         fifty_two_week_high: data.fifty_two_week_high,
+         fifty_two_week_low: data.fifty_two_week_low,
+         market_cap: data.market_cap
+      }
+   }
+
+   /// Loads snapshot quotes for many symbols at once.  Symbols that Yahoo!
+   /// doesn't know about are left out of the results.
+   ///
+   /// # Examples
+   ///
+   /// ``` no_run
+   /// use yahoo_finance::Snapshot;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    for quote in Snapshot::load(&["AAPL", "MSFT", "^DJI"]).await.unwrap() {
+   ///       println!("{} is at {:?} ({:?} / {:?})", quote.symbol, quote.price, quote.bid, quote.ask);
+   ///    }
+   /// }
+   /// ```
+   pub async fn load(symbols: &[&str]) -> Result<Vec<Snapshot>> {
+      client::shared()?.snapshots(symbols).await
+   }
+
+   pub(crate) async fn load_with(client: &Client, symbols: &[&str]) -> Result<Vec<Snapshot>> {
+      let calls = symbols.chunks(MAX_SYMBOLS_PER_CALL).map(|chunk| yahoo::load_quotes(client, chunk));
+
+      Ok(try_join_all(calls).await?
+         .into_iter()
+         .flatten()
+         .map(Snapshot::new)
+         .collect())
+   }
+}
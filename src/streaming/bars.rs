@@ -0,0 +1,211 @@
+use futures::{ stream, Stream, StreamExt };
+use market_finance::{ Bar, Quote, Timestamped };
+use std::collections::{ HashMap, VecDeque };
+use std::time::Duration;
+
+use crate::Result;
+
+use super::Session;
+
+/// A bar built from realtime quotes for a single symbol
+#[derive(Clone, Debug)]
+pub struct LiveBar {
+   /// The symbol the bar is for - ie. 'AAPL'
+   pub symbol: String,
+
+   /// The prices and volume - timestamped at the start of the interval
+   pub bar: Bar,
+
+   /// Whether the interval is over and the bar won't change anymore
+   pub complete: bool
+}
+impl Timestamped for LiveBar {
+   /// Gets the timestamp in millisecond accuracy
+   fn timestamp_millis(&self) -> i64 { self.bar.timestamp }
+}
+
+#[derive(Debug, Default)]
+struct SymbolState {
+   day_volume: Option<u64>,
+   bar: Option<Bar>
+}
+impl SymbolState {
+   /// Turns the cumulative day volume into the volume since the last quote
+   fn volume(&mut self, day_volume: u64) -> u64 {
+      // Yahoo! leaves the volume out when it's not known
+      if day_volume == 0 { return 0; }
+
+      let traded = match self.day_volume {
+         // we can't know how much of the volume so far belongs to this interval
+         None => 0,
+         Some(last) if day_volume >= last => day_volume - last,
+
+         // the volume went backwards - so a new day has started
+         Some(_) => day_volume
+      };
+      self.day_volume = Some(day_volume);
+      traded
+   }
+}
+
+/// Builds OHLCV bars for an interval from realtime quotes - separately for
+/// every symbol.
+///
+/// A bar is complete once a quote for the symbol arrives in a later interval,
+/// or when [`flush`](#method.flush) is called.  Bars are timestamped at the
+/// start of their interval, with intervals aligned to the epoch - so 1 minute
+/// bars start on the minute.
+///
+/// Yahoo! sends the volume traded so far today, which is turned into the
+/// volume traded during each bar.  Since the volume before the first quote for
+/// a symbol can't be split up, the first quote never adds any volume.  The
+/// volume of a late quote - one for an interval whose bar is already complete -
+/// is left out rather than being added to a later bar.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use futures::{ future, StreamExt };
+/// use std::time::Duration;
+/// use yahoo_finance::{ BarAggregator, Session, Streamer };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let streamer = Streamer::new(vec!["AAPL", "MSFT"]);
+///    let quotes = streamer.stream().await.unwrap();
+///
+///    BarAggregator::new(Duration::from_secs(60))
+///       .sessions(&[Session::Regular])
+///       .stream(quotes)
+///       .for_each(|bar| {
+///          if let Ok(bar) = bar { println!("{} {:?}", bar.symbol, bar.bar); }
+///          future::ready(())
+///       })
+///       .await;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BarAggregator {
+   interval: i64,
+   sessions: Option<Vec<Session>>,
+   partial: bool,
+   symbols: HashMap<String, SymbolState>
+}
+impl BarAggregator {
+   /// Creates an aggregator for bars covering `interval`
+   ///
+   /// # Panics
+   ///
+   /// This panics if `interval` is shorter than a millisecond.
+   pub fn new(interval: Duration) -> BarAggregator {
+      let interval = interval.as_millis().min(i64::MAX as u128) as i64;
+      assert!(interval > 0, "interval must be at least a millisecond");
+
+      BarAggregator { interval, sessions: None, partial: false, symbols: HashMap::new() }
+   }
+
+   /// Only builds bars from quotes in these trading sessions - ie. leaving out
+   /// pre-market and after hours trading.  Quotes from every session are used
+   /// by default.
+   ///
+   /// This is the same filter as [`Streamer::sessions`](struct.Streamer.html#method.sessions) -
+   /// a quote in `TradingSession::Other` is from extended hours.
+   pub fn sessions(mut self, sessions: &[Session]) -> BarAggregator {
+      self.sessions = Some(sessions.to_vec());
+      self
+   }
+
+   /// Also emits the bar as it currently stands after every quote, rather than
+   /// only once it's complete
+   pub fn partial(mut self, partial: bool) -> BarAggregator {
+      self.partial = partial;
+      self
+   }
+
+   /// Adds a quote - returning any bars it completes, followed by the bar it
+   /// updated if partial bars were asked for.
+   pub fn push(&mut self, quote: &Quote) -> Vec<LiveBar> {
+      let interval = self.interval;
+      let wanted = self.wanted(quote.session.into());
+      let state = self.symbols.entry(quote.symbol.clone()).or_default();
+
+      // the volume is tracked for every session so that the first quote in a
+      // wanted session doesn't carry the volume of the sessions before it
+      let volume = state.volume(quote.volume);
+      if !wanted { return Vec::new(); }
+
+      let start = quote.timestamp - quote.timestamp.rem_euclid(interval);
+      let mut bars = Vec::new();
+      match &mut state.bar {
+         // a late quote for an interval that's already done - its bar is gone, and
+         // its volume doesn't belong to the current one
+         Some(bar) if start < bar.timestamp => (),
+         Some(bar) if start == bar.timestamp => {
+            bar.high = bar.high.max(quote.price);
+            bar.low = bar.low.min(quote.price);
+            bar.close = quote.price;
+            bar.volume = Some(bar.volume.unwrap_or(0) + volume);
+         },
+         current => {
+            if let Some(bar) = current.take() {
+               bars.push(LiveBar { symbol: quote.symbol.clone(), bar, complete: true });
+            }
+            *current = Some(Bar {
+               timestamp: start,
+               open: quote.price,
+               high: quote.price,
+               low: quote.price,
+               close: quote.price,
+               volume: Some(volume)
+            });
+         }
+      }
+
+      if self.partial {
+         if let Some(bar) = state.bar {
+            bars.push(LiveBar { symbol: quote.symbol.clone(), bar, complete: false });
+         }
+      }
+      bars
+   }
+
+   /// Completes the bars that are still being built - ie. when the quotes end
+   pub fn flush(&mut self) -> Vec<LiveBar> {
+      let mut bars: Vec<LiveBar> = self.symbols.iter_mut()
+         .filter_map(|(symbol, state)| state.bar.take().map(|bar| LiveBar { symbol: symbol.clone(), bar, complete: true }))
+         .collect();
+      bars.sort_by(|a, b| a.bar.timestamp.cmp(&b.bar.timestamp).then_with(|| a.symbol.cmp(&b.symbol)));
+      bars
+   }
+
+   /// Turns a stream of quotes - ie. from [`Streamer::stream`](struct.Streamer.html#method.stream) -
+   /// into a stream of bars.  Errors in the quotes are passed along and the bars
+   /// that are still being built are completed when the quotes end.
+   pub fn stream<S>(self, quotes: S) -> impl Stream<Item = Result<LiveBar>>
+   where S: Stream<Item = Result<Quote>> {
+      let state = (Box::pin(quotes), self, VecDeque::new(), false);
+
+      stream::unfold(state, |(mut quotes, mut aggregator, mut pending, mut done)| async move {
+         loop {
+            if let Some(bar) = pending.pop_front() { return Some((bar, (quotes, aggregator, pending, done))); }
+            if done { return None; }
+
+            match quotes.next().await {
+               Some(Ok(quote)) => pending.extend(aggregator.push(&quote).into_iter().map(Ok)),
+               Some(Err(error)) => pending.push_back(Err(error)),
+               None => {
+                  pending.extend(aggregator.flush().into_iter().map(Ok));
+                  done = true;
+               }
+            }
+         }
+      })
+   }
+
+   fn wanted(&self, session: Session) -> bool {
+      match &self.sessions {
+         Some(sessions) => sessions.contains(&session),
+         None => true
+      }
+   }
+}
@@ -0,0 +1,211 @@
+use futures::{ stream, Stream, StreamExt };
+use market_finance::Quote;
+use std::collections::{ HashMap, VecDeque };
+use std::mem::discriminant;
+use std::time::Duration;
+use tokio::time::{ interval_at, Instant };
+
+use crate::Result;
+
+use super::Tick;
+
+/// An update that a [`Conflator`](struct.Conflator.html) can conflate - ie. a
+/// [`Quote`](struct.Quote.html) or a [`Tick`](struct.Tick.html)
+pub trait Conflate: Clone {
+   /// The symbol the update is for - updates are conflated per symbol
+   fn symbol(&self) -> &str;
+
+   /// Whether the update carries nothing new compared to the `previous` one
+   /// for its symbol - apart from the time it happened
+   fn unchanged(&self, previous: &Self) -> bool;
+
+   /// Folds in an `earlier` update that is being replaced by this one.  By
+   /// default the earlier update is simply dropped.
+   fn absorb(&mut self, _earlier: Self) {}
+}
+
+impl Conflate for Quote {
+   fn symbol(&self) -> &str { &self.symbol }
+
+   fn unchanged(&self, previous: &Quote) -> bool {
+      self.price == previous.price && self.volume == previous.volume && discriminant(&self.session) == discriminant(&previous.session)
+   }
+}
+
+/// Whether a value is unchanged - a tick leaves out the values that weren't in the update
+fn same<T: PartialEq>(value: &Option<T>, previous: &Option<T>) -> bool { value.is_none() || value == previous }
+
+/// Keeps an earlier value when the tick doesn't have one
+fn fill<T>(value: &mut Option<T>, earlier: Option<T>) { if value.is_none() { *value = earlier; } }
+
+impl Conflate for Tick {
+   fn symbol(&self) -> &str { &self.symbol }
+
+   fn unchanged(&self, previous: &Tick) -> bool {
+      self.price == previous.price
+         && self.session == previous.session
+         && same(&self.quote_type, &previous.quote_type)
+         && same(&self.change, &previous.change)
+         && same(&self.change_percent, &previous.change_percent)
+         && same(&self.open, &previous.open)
+         && same(&self.day_high, &previous.day_high)
+         && same(&self.day_low, &previous.day_low)
+         && same(&self.previous_close, &previous.previous_close)
+         && same(&self.day_volume, &previous.day_volume)
+         && same(&self.last_size, &previous.last_size)
+         && same(&self.bid, &previous.bid)
+         && same(&self.bid_size, &previous.bid_size)
+         && same(&self.ask, &previous.ask)
+         && same(&self.ask_size, &previous.ask_size)
+         && same(&self.price_hint, &previous.price_hint)
+         && same(&self.currency, &previous.currency)
+         && same(&self.exchange, &previous.exchange)
+         && same(&self.short_name, &previous.short_name)
+         && same(&self.market_cap, &previous.market_cap)
+         && same(&self.volume_24h, &previous.volume_24h)
+         && same(&self.volume_all_currencies, &previous.volume_all_currencies)
+         && same(&self.from_currency, &previous.from_currency)
+         && same(&self.last_market, &previous.last_market)
+         && same(&self.circulating_supply, &previous.circulating_supply)
+   }
+
+   /// Keeps the values of the earlier tick that this one doesn't have - so
+   /// nothing that was sent during the window is lost
+   fn absorb(&mut self, earlier: Tick) {
+      fill(&mut self.quote_type, earlier.quote_type);
+      fill(&mut self.change, earlier.change);
+      fill(&mut self.change_percent, earlier.change_percent);
+      fill(&mut self.open, earlier.open);
+      fill(&mut self.day_high, earlier.day_high);
+      fill(&mut self.day_low, earlier.day_low);
+      fill(&mut self.previous_close, earlier.previous_close);
+      fill(&mut self.day_volume, earlier.day_volume);
+      fill(&mut self.last_size, earlier.last_size);
+      fill(&mut self.bid, earlier.bid);
+      fill(&mut self.bid_size, earlier.bid_size);
+      fill(&mut self.ask, earlier.ask);
+      fill(&mut self.ask_size, earlier.ask_size);
+      fill(&mut self.price_hint, earlier.price_hint);
+      fill(&mut self.currency, earlier.currency);
+      fill(&mut self.exchange, earlier.exchange);
+      fill(&mut self.short_name, earlier.short_name);
+      fill(&mut self.market_cap, earlier.market_cap);
+      fill(&mut self.volume_24h, earlier.volume_24h);
+      fill(&mut self.volume_all_currencies, earlier.volume_all_currencies);
+      fill(&mut self.from_currency, earlier.from_currency);
+      fill(&mut self.last_market, earlier.last_market);
+      fill(&mut self.circulating_supply, earlier.circulating_supply);
+   }
+}
+
+/// Bounds the rate of realtime updates by only passing on the latest update
+/// for every symbol once per window.
+///
+/// Symbols are emitted in the order their first update in the window arrived.
+/// Symbols without an update in a window emit nothing.  With
+/// [`dedupe`](#method.dedupe) an update that has nothing new compared to the
+/// last one emitted for its symbol is dropped as well.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use futures::{ future, StreamExt };
+/// use std::time::Duration;
+/// use yahoo_finance::{ Conflator, Streamer };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let streamer = Streamer::new(vec!["AAPL", "MSFT"]);
+///    let quotes = streamer.stream().await.unwrap();
+///
+///    Conflator::new(Duration::from_millis(250))
+///       .dedupe(true)
+///       .stream(quotes)
+///       .for_each(|quote| { println!("{:?}", quote); future::ready(()) })
+///       .await;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Conflator<T: Conflate> {
+   window: Duration,
+   dedupe: bool,
+   order: Vec<String>,
+   pending: HashMap<String, T>,
+   emitted: HashMap<String, T>
+}
+impl<T: Conflate> Conflator<T> {
+   /// Creates a conflator that passes on updates once every `window`
+   ///
+   /// # Panics
+   ///
+   /// This panics if `window` is zero.
+   pub fn new(window: Duration) -> Conflator<T> {
+      assert!(window > Duration::from_secs(0), "window must be positive");
+      Conflator { window, dedupe: false, order: Vec::new(), pending: HashMap::new(), emitted: HashMap::new() }
+   }
+
+   /// Drops updates that have nothing new compared to the last one emitted for
+   /// their symbol
+   pub fn dedupe(mut self, dedupe: bool) -> Conflator<T> {
+      self.dedupe = dedupe;
+      self
+   }
+
+   /// Adds an update - replacing any update for the symbol that's waiting for
+   /// the end of the window
+   pub fn push(&mut self, mut update: T) {
+      match self.pending.remove(update.symbol()) {
+         Some(earlier) => update.absorb(earlier),
+         None => self.order.push(update.symbol().to_string())
+      }
+      self.pending.insert(update.symbol().to_string(), update);
+   }
+
+   /// Ends the window - returning the latest update for every symbol that had one
+   pub fn flush(&mut self) -> Vec<T> {
+      let mut updates = Vec::new();
+      for symbol in self.order.drain(..) {
+         let update = match self.pending.remove(&symbol) { Some(update) => update, None => continue };
+         if self.dedupe {
+            if let Some(previous) = self.emitted.get(&symbol) {
+               if update.unchanged(previous) { continue; }
+            }
+            self.emitted.insert(symbol, update.clone());
+         }
+         updates.push(update);
+      }
+      updates
+   }
+
+   /// Conflates a stream of updates - ie. from [`Streamer::stream`](struct.Streamer.html#method.stream).
+   /// Errors are passed along right away and the waiting updates are flushed
+   /// when the updates end.
+   pub fn stream<S>(self, updates: S) -> impl Stream<Item = Result<T>>
+   where S: Stream<Item = Result<T>> {
+      // the timer is started on the first poll - since it needs a runtime and the
+      // stream can be built outside of one
+      let state = (Box::pin(updates.fuse()), self, None, VecDeque::new(), false);
+
+      stream::unfold(state, |(mut updates, mut conflator, mut timer, mut ready, mut done)| async move {
+         loop {
+            if let Some(update) = ready.pop_front() { return Some((update, (updates, conflator, timer, ready, done))); }
+            if done { return None; }
+
+            let window = conflator.window;
+            let ticks = timer.get_or_insert_with(|| interval_at(Instant::now() + window, window));
+
+            tokio::select! {
+               update = updates.next() => match update {
+                  Some(Ok(update)) => conflator.push(update),
+                  Some(Err(error)) => ready.push_back(Err(error)),
+                  None => {
+                     ready.extend(conflator.flush().into_iter().map(Ok));
+                     done = true;
+                  }
+               },
+               _ = ticks.tick() => ready.extend(conflator.flush().into_iter().map(Ok))
+            }
+         }
+      })
+   }
+}
@@ -0,0 +1,268 @@
+use futures::{ SinkExt, StreamExt };
+use snafu::ResultExt;
+use std::sync::{ Arc, Mutex };
+use std::time::{ Duration, Instant };
+use tokio::net::TcpStream;
+use tokio::sync::{ broadcast, mpsc, watch };
+use tokio_tungstenite::{ connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream };
+
+use crate::{ error, retry, Result };
+use crate::yahoo::PricingData;
+
+use super::{ decode, Recorder, StreamStats };
+use super::subscriptions::{ Subs, Subscriptions };
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// How long we wait for Yahoo! to agree to close the connection when stopping
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Controls how a [`Streamer`](struct.Streamer.html) reconnects when Yahoo!
+/// drops the connection.
+///
+/// The delay between attempts doubles (by default) with every attempt, up to
+/// `max_backoff`.  A successful reconnect starts the next outage back at
+/// `initial_backoff`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use yahoo_finance::{ ReconnectPolicy, Streamer };
+///
+/// let streamer = Streamer::new(vec!["AAPL"])
+///    .reconnect(ReconnectPolicy { max_attempts: Some(10), initial_backoff: Duration::from_secs(1), ..ReconnectPolicy::default() });
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReconnectPolicy {
+   /// The most reconnect attempts for a single outage before giving up - or
+   /// `None` to keep trying forever
+   pub max_attempts: Option<u32>,
+
+   /// How long to wait before the first reconnect attempt
+   pub initial_backoff: Duration,
+
+   /// The longest we'll wait between attempts
+   pub max_backoff: Duration,
+
+   /// How much the wait grows by after each attempt
+   pub multiplier: f64,
+
+   /// Whether to randomize the wait so that many streamers don't reconnect in lock step
+   pub jitter: bool
+}
+impl ReconnectPolicy {
+   /// The time to wait before a reconnect attempt (starting at 1)
+   fn backoff(&self, attempt: u32) -> Duration {
+      retry::exponential_backoff(self.initial_backoff, self.max_backoff, self.multiplier, self.jitter, attempt)
+   }
+
+   /// Determines if we've tried enough times and should give up
+   #[allow(clippy::unnecessary_map_or)] // is_some_and is too new for the Rust versions we support
+   fn exhausted(&self, attempt: u32) -> bool {
+      self.max_attempts.map_or(false, |max| attempt > max)
+   }
+}
+impl Default for ReconnectPolicy {
+   fn default() -> ReconnectPolicy {
+      ReconnectPolicy {
+         max_attempts: None,
+         initial_backoff: Duration::from_millis(500),
+         max_backoff: Duration::from_secs(30),
+         multiplier: 2.0,
+         jitter: true
+      }
+   }
+}
+
+/// The state of a streamer's connection to Yahoo!
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConnectionState {
+   /// Connected and subscribed to the symbols
+   Connected,
+
+   /// The connection was lost and we are about to make reconnect attempt
+   /// number `attempt` (starting at 1)
+   Reconnecting { attempt: u32 },
+
+   /// The connection was lost and the reconnect policy has given up - the
+   /// stream of quotes ends
+   GaveUp
+}
+
+/// Connects to Yahoo! and subscribes to the symbols - if there are any
+pub(crate) async fn connect(url: &str, subs: &[String]) -> Result<Socket> {
+   let (mut socket, _) = connect_async(url).await.context(error::StreamConnect { url })?;
+
+   if !subs.is_empty() { send(&mut socket, &Subs::Subscribe(subs.to_vec())).await?; }
+   Ok(socket)
+}
+
+/// Sends a subscription change to Yahoo!
+async fn send(socket: &mut Socket, subs: &Subs) -> Result<()> {
+   let message = serde_json::to_string(subs).context(error::BadData)?;
+   Ok(socket.send(Message::Text(message)).await.context(error::StreamProtocol)?)
+}
+
+/// The next subscription change - which never comes once the changes are closed
+async fn next_command(commands: &mut Option<mpsc::UnboundedReceiver<Subs>>) -> Option<Subs> {
+   match commands {
+      Some(commands) => commands.recv().await,
+      None => futures::future::pending().await
+   }
+}
+
+/// Resolves once the streamer is stopped - which never happens once the
+/// streamer and all of its handles are gone
+async fn stop_requested(shutdown: &mut watch::Receiver<bool>) {
+   loop {
+      if *shutdown.borrow() { return; }
+      if shutdown.recv().await.is_none() { futures::future::pending::<()>().await; }
+   }
+}
+
+/// Closes the connection cleanly - giving Yahoo! a little while to agree
+async fn close(socket: &mut Socket) {
+   if socket.close(None).await.is_err() { return; }
+   let _ = tokio::time::timeout(CLOSE_TIMEOUT, async { while let Some(Ok(_)) = socket.next().await {} }).await;
+}
+
+/// Resolves once nothing has been received for `timeout` since `last_seen` -
+/// or never without a timeout
+async fn stale(timeout: Option<Duration>, last_seen: Instant) {
+   match timeout {
+      Some(timeout) => tokio::time::delay_until(tokio::time::Instant::from_std(last_seen + timeout)).await,
+      None => futures::future::pending().await
+   }
+}
+
+/// Owns the connection to Yahoo! - forwarding the frames it receives and
+/// reconnecting when the connection is lost
+pub(crate) struct Driver {
+   pub url: String,
+   pub subs: Arc<Subscriptions>,
+   pub commands: Option<mpsc::UnboundedReceiver<Subs>>,
+   pub reconnect: Option<ReconnectPolicy>,
+   pub recorder: Option<Recorder>,
+   pub stale_timeout: Option<Duration>,
+   pub stats: Arc<Mutex<StreamStats>>,
+   pub states: broadcast::Sender<ConnectionState>,
+   pub shutdown: watch::Receiver<bool>,
+   pub frames: mpsc::UnboundedSender<Result<PricingData>>
+}
+impl Driver {
+   pub async fn run(mut self, mut socket: Socket) {
+      loop {
+         if !self.pump(&mut socket).await { return; }
+
+         socket = match self.reopen().await {
+            Some(socket) => socket,
+            None => return
+         };
+      }
+   }
+
+   /// Forwards frames and subscription changes until the connection is lost -
+   /// returning whether we should try to reconnect
+   async fn pump(&mut self, socket: &mut Socket) -> bool {
+      // anything from Yahoo! - including pings and heartbeats - shows the connection is alive
+      let mut last_seen = Instant::now();
+
+      loop {
+         let message = tokio::select! {
+            _ = stop_requested(&mut self.shutdown) => {
+               close(socket).await;
+               return false;
+            },
+            _ = stale(self.stale_timeout, last_seen) => {
+               // the connection is silently dead - so reconnect if the policy allows it
+               let timeout = self.stale_timeout.unwrap_or_default();
+               let _ = self.frames.send(Err(error::InnerError::StreamStale { timeout }.into()));
+               return !self.stopped();
+            },
+            message = socket.next() => match message { Some(message) => message, None => break },
+            command = next_command(&mut self.commands) => {
+               match command {
+                  Some(command) => if let Err(error) = send(socket, &command).await {
+                     // the connection is broken - the reconnect picks up the change
+                     let _ = self.frames.send(Err(error));
+                     return !self.stopped();
+                  },
+                  // the streamer and all of its handles are gone - so no more changes
+                  None => self.commands = None
+               }
+               continue;
+            }
+         };
+
+         last_seen = Instant::now();
+
+         let frame = match message {
+            Err(source) => {
+               // the connection is broken - so reconnect if the policy allows it
+               let _ = self.frames.send(Err(error::InnerError::StreamProtocol { source }.into()));
+               return !self.stopped();
+            },
+            Ok(Message::Ping(data)) => { let _ = socket.send(Message::Pong(data)).await; continue; },
+            Ok(Message::Close(_)) => break,
+            Ok(Message::Text(value)) => Ok(value),
+            Ok(Message::Binary(value)) => String::from_utf8(value)
+               .map_err(|e| error::InnerError::StreamDecode { reason: e.to_string() }.into()),
+            _ => continue
+         };
+
+         // a failed recording shouldn't stop the stream - but the consumer should know
+         if let (Some(recorder), Ok(frame)) = (&self.recorder, &frame) {
+            if let Err(error) = recorder.record(frame).await {
+               if self.frames.send(Err(error)).is_err() { return false; }
+            }
+         }
+
+         let data = frame.and_then(|frame| decode(&frame));
+         if !self.stats.lock().unwrap().observe(&data) { continue; }
+
+         // nobody is listening anymore
+         if self.frames.send(data).is_err() { return false; }
+      }
+
+      !self.stopped()
+   }
+
+   /// Reconnects according to the policy - giving up with `None`
+   async fn reopen(&mut self) -> Option<Socket> {
+      let policy = self.reconnect.clone()?;
+
+      let mut attempt = 1;
+      loop {
+         if policy.exhausted(attempt) {
+            let _ = self.states.send(ConnectionState::GaveUp);
+            return None;
+         }
+
+         let _ = self.states.send(ConnectionState::Reconnecting { attempt });
+         let (url, subs) = (&self.url, &self.subs);
+         let connected = tokio::select! {
+            _ = stop_requested(&mut self.shutdown) => return None,
+            connected = async {
+               tokio::time::delay_for(policy.backoff(attempt)).await;
+               connect(url, &subs.symbols()).await
+            } => connected
+         };
+
+         match connected {
+            Ok(socket) => {
+               self.stats.lock().unwrap().reconnects += 1;
+               let _ = self.states.send(ConnectionState::Connected);
+               return Some(socket);
+            },
+            Err(error) => {
+               // let the consumer know why it's taking so long
+               if self.frames.send(Err(error)).is_err() { return None; }
+               attempt += 1;
+            }
+         }
+      }
+   }
+
+   fn stopped(&self) -> bool { *self.shutdown.borrow() }
+}
@@ -0,0 +1,263 @@
+use futures::{ Stream, StreamExt };
+use std::collections::{ HashMap, HashSet, VecDeque };
+use std::pin::Pin;
+use std::sync::{ Arc, Mutex, Weak };
+use std::task::{ Context, Poll, Waker };
+
+use crate::{ error, Result };
+
+use super::{ StreamHandle, Streamer, Tick };
+
+/// What a [`QuoteHub`](struct.QuoteHub.html) does when a consumer falls too
+/// far behind
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LagPolicy {
+   /// Throw away the consumer's oldest ticks to make room for new ones
+   DropOldest,
+
+   /// Disconnect the consumer - its stream ends with a `HubLagged` error
+   Disconnect
+}
+
+/// The ticks waiting for a consumer
+#[derive(Debug, Default)]
+struct Queue {
+   items: VecDeque<Result<Tick>>,
+   closed: bool,
+   waker: Option<Waker>
+}
+impl Queue {
+   fn push(&mut self, item: Result<Tick>) {
+      self.items.push_back(item);
+      if let Some(waker) = self.waker.take() { waker.wake(); }
+   }
+
+   fn close(&mut self) {
+      self.closed = true;
+      if let Some(waker) = self.waker.take() { waker.wake(); }
+   }
+}
+
+#[derive(Debug)]
+struct Consumer {
+   symbols: HashSet<String>,
+   queue: Arc<Mutex<Queue>>
+}
+
+#[derive(Debug, Default)]
+struct State {
+   refs: HashMap<String, usize>,
+   consumers: HashMap<u64, Consumer>,
+   next_id: u64,
+   ended: bool
+}
+impl State {
+   /// Drops a consumer's references - returning the symbols nobody wants anymore
+   fn release(&mut self, symbols: &HashSet<String>) -> Vec<String> {
+      let mut unwanted = Vec::new();
+      for symbol in symbols {
+         if let Some(count) = self.refs.get_mut(symbol) {
+            *count -= 1;
+            if *count == 0 {
+               self.refs.remove(symbol);
+               unwanted.push(symbol.clone());
+            }
+         }
+      }
+      unwanted
+   }
+}
+
+#[derive(Debug)]
+struct Hub {
+   upstream: StreamHandle,
+   capacity: usize,
+   lag: LagPolicy,
+   state: Mutex<State>
+}
+impl Hub {
+   /// Hands a tick from upstream to the consumers that want it
+   fn dispatch(&self, item: Result<Tick>) {
+      let mut state = self.state.lock().unwrap();
+
+      // errors aren't cloneable - so every consumer gets a description instead
+      let reason = item.as_ref().err().map(|e| e.to_string());
+      let mut disconnected = Vec::new();
+      for (id, consumer) in &state.consumers {
+         let item = match (&item, &reason) {
+            (Ok(tick), _) if consumer.symbols.contains(&tick.symbol) => Ok(tick.clone()),
+            (Err(_), Some(reason)) => Err(error::InnerError::HubUpstream { reason: reason.clone() }.into()),
+            _ => continue
+         };
+
+         let mut queue = consumer.queue.lock().unwrap();
+         if queue.items.len() >= self.capacity {
+            match self.lag {
+               LagPolicy::DropOldest => { queue.items.pop_front(); },
+               LagPolicy::Disconnect => {
+                  queue.push(Err(error::InnerError::HubLagged { capacity: self.capacity }.into()));
+                  queue.close();
+                  disconnected.push(*id);
+                  continue;
+               }
+            }
+         }
+         queue.push(item);
+      }
+
+      let mut unwanted = Vec::new();
+      for id in disconnected {
+         if let Some(consumer) = state.consumers.remove(&id) { unwanted.extend(state.release(&consumer.symbols)); }
+      }
+      self.unsubscribe(unwanted);
+   }
+
+   /// Ends every consumer's stream once upstream has ended
+   fn end(&self) {
+      let mut state = self.state.lock().unwrap();
+      state.ended = true;
+      for consumer in state.consumers.values() { consumer.queue.lock().unwrap().close(); }
+   }
+
+   fn unsubscribe(&self, symbols: Vec<String>) {
+      if symbols.is_empty() { return; }
+      self.upstream.unsubscribe(&symbols.iter().map(|s| s.as_str()).collect::<Vec<_>>());
+   }
+}
+impl Drop for Hub {
+   fn drop(&mut self) {
+      // nobody is left to use the connection
+      self.upstream.stop();
+   }
+}
+
+/// Shares one connection to Yahoo! between many consumers that each want
+/// quotes for their own symbols.
+///
+/// Each symbol is subscribed to upstream while at least one consumer wants it.
+/// Every consumer has its own queue of up to `capacity` ticks, and the
+/// [`LagPolicy`](enum.LagPolicy.html) decides what happens to a consumer that
+/// lets its queue fill up - so a slow consumer never holds up the others.
+/// Cloning a hub shares it.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use futures::StreamExt;
+/// use yahoo_finance::{ LagPolicy, QuoteHub, ReconnectPolicy, Streamer };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let streamer = Streamer::new(Vec::new()).reconnect(ReconnectPolicy::default());
+///    let hub = QuoteHub::start(streamer, 1024, LagPolicy::DropOldest).await.unwrap();
+///
+///    let mut tech = hub.subscribe(&["AAPL", "MSFT"]);
+///    let mut apple = hub.subscribe(&["AAPL"]);
+///
+///    println!("{:?} / {:?}", tech.next().await, apple.next().await);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct QuoteHub {
+   hub: Arc<Hub>
+}
+impl QuoteHub {
+   /// Connects the streamer to Yahoo! and starts handing out its ticks.
+   ///
+   /// The streamer's connection settings - ie. reconnects or recordings - are
+   /// used for the shared connection.  Any symbols the streamer already has
+   /// stay subscribed for as long as the hub is running.
+   ///
+   /// # Panics
+   ///
+   /// This panics if `capacity` is zero.
+   pub async fn start(streamer: Streamer, capacity: usize, lag: LagPolicy) -> Result<QuoteHub> {
+      assert!(capacity > 0, "capacity must be positive");
+
+      let upstream = streamer.handle();
+      let mut state = State::default();
+      for symbol in upstream.symbols() { state.refs.insert(symbol, 1); }
+
+      let mut ticks = Box::pin(streamer.stream_ticks().await?);
+      let hub = Arc::new(Hub { upstream, capacity, lag, state: Mutex::new(state) });
+
+      // only hold on to the hub weakly so that it goes away with its consumers
+      let weak: Weak<Hub> = Arc::downgrade(&hub);
+      tokio::spawn(async move {
+         while let Some(item) = ticks.next().await {
+            match weak.upgrade() {
+               Some(hub) => hub.dispatch(item),
+               None => return
+            }
+         }
+         if let Some(hub) = weak.upgrade() { hub.end(); }
+      });
+
+      Ok(QuoteHub { hub })
+   }
+
+   /// Streams the ticks for some symbols - subscribing to any that nobody else
+   /// wants yet.  The symbols are released when the stream is dropped.
+   pub fn subscribe(&self, symbols: &[&str]) -> HubStream {
+      let symbols: HashSet<String> = symbols.iter().map(|s| s.to_string()).collect();
+      let queue = Arc::new(Mutex::new(Queue::default()));
+
+      let mut state = self.hub.state.lock().unwrap();
+      let id = state.next_id;
+      state.next_id += 1;
+
+      if state.ended {
+         queue.lock().unwrap().close();
+      } else {
+         let mut wanted = Vec::new();
+         for symbol in &symbols {
+            let count = state.refs.entry(symbol.clone()).or_insert(0);
+            *count += 1;
+            if *count == 1 { wanted.push(symbol.as_str()); }
+         }
+         if !wanted.is_empty() { self.hub.upstream.subscribe(&wanted); }
+
+         state.consumers.insert(id, Consumer { symbols: symbols.clone(), queue: queue.clone() });
+      }
+
+      HubStream { hub: self.hub.clone(), id, queue }
+   }
+
+   /// The symbols that are currently subscribed to upstream
+   pub fn symbols(&self) -> Vec<String> { self.hub.upstream.symbols() }
+
+   /// Closes the shared connection - which ends every consumer's stream
+   pub fn stop(&self) { self.hub.upstream.stop(); }
+}
+
+/// The ticks for one consumer of a [`QuoteHub`](struct.QuoteHub.html)
+#[derive(Debug)]
+pub struct HubStream {
+   hub: Arc<Hub>,
+   id: u64,
+   queue: Arc<Mutex<Queue>>
+}
+impl Stream for HubStream {
+   type Item = Result<Tick>;
+
+   fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Tick>>> {
+      let mut queue = self.queue.lock().unwrap();
+
+      if let Some(item) = queue.items.pop_front() { return Poll::Ready(Some(item)); }
+      if queue.closed { return Poll::Ready(None); }
+
+      queue.waker = Some(cx.waker().clone());
+      Poll::Pending
+   }
+}
+impl Drop for HubStream {
+   fn drop(&mut self) {
+      let mut state = self.hub.state.lock().unwrap();
+      let unwanted = match state.consumers.remove(&self.id) {
+         Some(consumer) => state.release(&consumer.symbols),
+         None => return
+      };
+      drop(state);
+      self.hub.unsubscribe(unwanted);
+   }
+}
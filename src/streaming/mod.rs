@@ -0,0 +1,209 @@
+use futures::{ future, Stream, StreamExt };
+use protobuf::Message as _;
+use std::future::Future;
+use std::sync::{ Arc, Mutex };
+use std::time::Duration;
+use tokio::sync::{ broadcast, mpsc, watch };
+
+use crate::{ error, Result };
+use crate::yahoo::{ PricingData, PricingData_MarketHoursType, PricingData_QuoteType };
+
+use super::{ Quote };
+
+mod connection;
+pub use connection::{ ConnectionState, ReconnectPolicy };
+
+mod bars;
+pub use bars::{ BarAggregator, LiveBar };
+
+mod conflate;
+pub use conflate::{ Conflate, Conflator };
+
+mod tick;
+pub use tick::{ CryptoDetails, OptionDetails, Tick, TypedTick };
+
+mod hub;
+pub use hub::{ HubStream, LagPolicy, QuoteHub };
+
+mod recording;
+pub use recording::{ Recorder, Replay, ReplaySpeed };
+
+mod session;
+pub use session::{ Session, SessionChange, SessionTracker };
+
+mod stats;
+pub use stats::StreamStats;
+
+mod subscriptions;
+pub use subscriptions::StreamHandle;
+use subscriptions::Subscriptions;
+
+pub(crate) const STREAMING_URL: &str = "wss://streamer.finance.yahoo.com";
+
+pub(crate) fn convert_session(value: PricingData_MarketHoursType) -> Session {
+   match value {
+      PricingData_MarketHoursType::PRE_MARKET => Session::PreMarket,
+      PricingData_MarketHoursType::REGULAR_MARKET => Session::Regular,
+      PricingData_MarketHoursType::POST_MARKET => Session::AfterHours,
+      PricingData_MarketHoursType::EXTENDED_HOURS_MARKET => Session::ExtendedHours
+   }
+}
+
+/// Decodes a base64 encoded protobuf message from Yahoo!
+fn decode(frame: &str) -> Result<PricingData> {
+   let bytes = base64::decode(frame).map_err(|e| error::InnerError::StreamDecode { reason: e.to_string() })?;
+   Ok(PricingData::parse_from_bytes(&bytes).map_err(|e| error::InnerError::StreamDecode { reason: e.to_string() })?)
+}
+
+/// Determines if a decoded message is only Yahoo! showing the connection is alive
+fn is_heartbeat(data: &PricingData) -> bool { data.quoteType == PricingData_QuoteType::HEARTBEAT }
+
+/// Realtime price quote streamer
+///
+/// To use it:
+/// 1. Create a new streamer for some symbols with `Streamer::new(vec!["AAPL"]);`
+/// 1. Optionally keep a [`handle`](#method.handle) to change the symbols while streaming
+/// 1. Optionally have it reconnect when the connection drops with `.reconnect(ReconnectPolicy::default())`
+/// 1. Connect with `streamer.stream().await?;` - which fails if Yahoo! cannot be reached
+/// 1. Read the quotes from the stream - a message that cannot be read is an error in the stream
+/// 1. Stop streaming with `streamer.stop()` or the handle - which closes the connection and ends the stream
+pub struct Streamer {
+   url: String,
+   subs: Arc<Subscriptions>,
+   reconnect: Option<ReconnectPolicy>,
+   recorder: Option<Recorder>,
+   stale_timeout: Option<Duration>,
+   sessions: Option<Vec<Session>>,
+   stats: Arc<Mutex<StreamStats>>,
+   states: broadcast::Sender<ConnectionState>,
+   shutdown: Arc<watch::Sender<bool>>,
+   stopped: watch::Receiver<bool>
+}
+impl Streamer {
+   pub fn new(symbols: Vec<&str>) -> Streamer {
+      Streamer::with_url(symbols, STREAMING_URL)
+   }
+
+   pub(crate) fn with_url(symbols: Vec<&str>, url: &str) -> Streamer {
+      let subs = Arc::new(Subscriptions::new(symbols.iter().map(|s| s.to_string()).collect()));
+
+      let (states, _) = broadcast::channel(16);
+      let (shutdown, stopped) = watch::channel(false);
+      Streamer { url: url.to_string(), subs, reconnect: None, recorder: None, stale_timeout: None, sessions: None, stats: Default::default(), states, shutdown: Arc::new(shutdown), stopped }
+   }
+
+   /// Reconnects and resubscribes to the symbols according to `policy` when
+   /// the connection to Yahoo! is lost.  Without a policy the stream ends when
+   /// the connection does.
+   pub fn reconnect(mut self, policy: ReconnectPolicy) -> Streamer {
+      self.reconnect = Some(policy);
+      self
+   }
+
+   /// Writes every frame received from Yahoo! to a recording that can be
+   /// played back later with a [`Replay`](struct.Replay.html)
+   pub fn record(mut self, recorder: Recorder) -> Streamer {
+      self.recorder = Some(recorder);
+      self
+   }
+
+   /// Treats the connection as dead when nothing - not even a heartbeat or a
+   /// ping - is received from Yahoo! for `timeout`.  A dead connection is an
+   /// error in the stream and then reconnected if there's a reconnect policy.
+   ///
+   /// Keep in mind that Yahoo! only sends updates when prices change, so the
+   /// timeout should be longer than the quietest expected market.
+   pub fn stale_timeout(mut self, timeout: Duration) -> Streamer {
+      self.stale_timeout = Some(timeout);
+      self
+   }
+
+   /// Only streams updates from these trading sessions - ie. leaving out
+   /// pre-market and after hours trading.  Updates from every session are
+   /// streamed by default.
+   pub fn sessions(mut self, sessions: &[Session]) -> Streamer {
+      self.sessions = Some(sessions.to_vec());
+      self
+   }
+
+   /// How the connection has been doing so far
+   pub fn stats(&self) -> StreamStats { self.stats.lock().unwrap().clone() }
+
+   /// A handle for subscribing to and unsubscribing from symbols, or stopping,
+   /// while streaming
+   pub fn handle(&self) -> StreamHandle { StreamHandle::new(self.subs.clone(), self.shutdown.clone()) }
+
+   /// Watches the state of the connection to Yahoo!
+   ///
+   /// Only changes that happen after this is called are seen - so call it
+   /// before [`stream`](#method.stream) to see the initial connection.
+   pub fn connection_states(&self) -> impl Stream<Item = ConnectionState> {
+      self.states.subscribe().filter_map(|state| future::ready(state.ok()))
+   }
+
+   /// Connects to Yahoo! and streams quotes for the symbols.
+   ///
+   /// Failing to connect is returned as an error.  Once connected, problems with
+   /// individual messages are returned as errors in the stream rather than ending it.
+   pub async fn stream(&self) -> Result<impl Stream<Item = Result<Quote>>> {
+      Ok(self.stream_ticks().await?.map(|tick| tick.map(|tick| tick.quote())))
+   }
+
+   /// Same as [`stream`](#method.stream) but with everything Yahoo! sends in
+   /// each update - ie. bid / ask, day high / low, etc.
+   pub async fn stream_ticks(&self) -> Result<impl Stream<Item = Result<Tick>>> {
+      Ok(self.stream_data().await?.map(|data| data.map(Tick::new)))
+   }
+
+   /// Same as [`stream_ticks`](#method.stream_ticks) but with the option and
+   /// cryptocurrency values broken out for those kinds of symbols
+   pub async fn stream_typed(&self) -> Result<impl Stream<Item = Result<TypedTick>>> {
+      Ok(self.stream_data().await?.map(|data| data.map(TypedTick::new)))
+   }
+
+   /// Connects to Yahoo! and streams the decoded updates
+   async fn stream_data(&self) -> Result<impl Stream<Item = Result<PricingData>>> {
+      // listen before connecting so that no changes are missed
+      let (symbols, commands) = self.subs.listen();
+      let socket = connection::connect(&self.url, &symbols).await?;
+      let _ = self.states.send(ConnectionState::Connected);
+
+      let (frames, rx) = mpsc::unbounded_channel();
+      let driver = connection::Driver {
+         url: self.url.clone(),
+         subs: self.subs.clone(),
+         commands: Some(commands),
+         reconnect: self.reconnect.clone(),
+         recorder: self.recorder.clone(),
+         stale_timeout: self.stale_timeout,
+         stats: self.stats.clone(),
+         states: self.states.clone(),
+         shutdown: self.stopped.clone(),
+         frames
+      };
+      tokio::spawn(driver.run(socket));
+
+      let sessions = self.sessions.clone();
+      Ok(rx.filter(move |data| future::ready(match (data, &sessions) {
+         (Ok(data), Some(sessions)) => sessions.contains(&convert_session(data.marketHours)),
+         _ => true
+      })))
+   }
+
+   /// Stops streaming right away - closing the connection to Yahoo! and
+   /// ending the streams.  A stopped streamer can't be started again.
+   pub fn stop(&self) {
+      let _ = self.shutdown.broadcast(true);
+   }
+
+   /// Stops streaming once `signal` completes - ie. a ctrl-c handler or a
+   /// cancellation token.  This has to be called within a tokio runtime.
+   pub fn stop_when<F>(&self, signal: F)
+   where F: Future<Output = ()> + Send + 'static {
+      let handle = self.handle();
+      tokio::spawn(async move {
+         signal.await;
+         handle.stop();
+      });
+   }
+}
@@ -0,0 +1,183 @@
+use chrono::Utc;
+use futures::{ future, stream, Stream, StreamExt };
+use snafu::ResultExt;
+use std::fs::File;
+use std::io::{ BufWriter, Write };
+use std::path::{ Path, PathBuf };
+use std::sync::{ Arc, Mutex };
+use std::time::Duration;
+use tokio::io::{ AsyncBufReadExt, BufReader, Lines };
+
+use crate::{ error, Quote, Result };
+use crate::yahoo::PricingData;
+
+use super::{ decode, is_heartbeat, Tick, TypedTick };
+
+/// Writes the raw frames a [`Streamer`](struct.Streamer.html) receives to a
+/// file so that they can be played back later with a [`Replay`](struct.Replay.html).
+///
+/// Every frame is written on its own line as the time it was received in
+/// milliseconds since the epoch, a space and the base64 frame exactly as Yahoo!
+/// sent it.  Cloning a recorder shares the file.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ Recorder, Streamer };
+///
+/// let streamer = Streamer::new(vec!["AAPL"]).record(Recorder::create("aapl.rec").unwrap());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Recorder {
+   path: String,
+   file: Arc<Mutex<BufWriter<File>>>
+}
+impl Recorder {
+   /// Creates the file to record to - replacing it if it already exists
+   pub fn create<P: AsRef<Path>>(path: P) -> Result<Recorder> {
+      let path = path.as_ref().display().to_string();
+      let file = File::create(&path).context(error::Recording { path: &path })?;
+
+      Ok(Recorder { path, file: Arc::new(Mutex::new(BufWriter::new(file))) })
+   }
+
+   /// Writes a frame received right now - on the blocking thread pool, so
+   /// that a slow disk doesn't hold up the stream
+   pub(crate) async fn record(&self, frame: &str) -> Result<()> {
+      let line = format!("{} {}", Utc::now().timestamp_millis(), frame);
+      let file = self.file.clone();
+
+      // flush every frame so that nothing is lost if we crash - which is when
+      // a recording is most useful
+      let written = tokio::task::spawn_blocking(move || {
+         let mut file = file.lock().unwrap();
+         writeln!(file, "{}", line).and_then(|_| file.flush())
+      }).await;
+
+      match written {
+         Ok(written) => Ok(written.context(error::Recording { path: &self.path })?),
+         Err(e) => Err(error::InnerError::InternalLogic { reason: format!("writing to '{}' failed - {}", self.path, e) }.into())
+      }
+   }
+}
+
+/// How quickly a [`Replay`](struct.Replay.html) plays back a recording
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplaySpeed {
+   /// With the same gaps between frames as when they were recorded
+   Original,
+
+   /// Some multiple of the original speed - ie. 2.0 is twice as fast
+   Factor(f64),
+
+   /// Without any gaps between frames
+   AsFastAsPossible
+}
+impl ReplaySpeed {
+   /// How long to wait between frames that were received `gap` milliseconds apart
+   fn delay(self, gap: i64) -> Option<Duration> {
+      let gap = Duration::from_millis(gap.max(0) as u64);
+      match self {
+         ReplaySpeed::Original => Some(gap),
+         ReplaySpeed::Factor(factor) if factor > 0.0 => Some(gap.div_f64(factor)),
+         _ => None
+      }
+   }
+}
+
+/// Plays back a recording made with a [`Recorder`](struct.Recorder.html) -
+/// decoding the frames exactly like a [`Streamer`](struct.Streamer.html) does.
+///
+/// The streams mirror the ones from a streamer, so code that uses a streamer
+/// can be tested offline against a recording.  Lines that can't be read are
+/// errors in the stream rather than ending it, and heartbeats are skipped.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use futures::{ future, StreamExt };
+/// use yahoo_finance::{ Replay, ReplaySpeed };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    Replay::new("aapl.rec").speed(ReplaySpeed::Factor(10.0))
+///       .stream().await.unwrap()
+///       .for_each(|quote| { println!("{:?}", quote); future::ready(()) })
+///       .await;
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Replay {
+   path: PathBuf,
+   speed: ReplaySpeed
+}
+impl Replay {
+   /// Creates a replay of a recording at its original speed
+   pub fn new<P: AsRef<Path>>(path: P) -> Replay {
+      Replay { path: path.as_ref().to_path_buf(), speed: ReplaySpeed::Original }
+   }
+
+   /// Changes how quickly the recording is played back
+   pub fn speed(mut self, speed: ReplaySpeed) -> Replay {
+      self.speed = speed;
+      self
+   }
+
+   /// Same as [`Streamer::stream`](struct.Streamer.html#method.stream) but from the recording
+   pub async fn stream(&self) -> Result<impl Stream<Item = Result<Quote>>> {
+      Ok(self.stream_ticks().await?.map(|tick| tick.map(|tick| tick.quote())))
+   }
+
+   /// Same as [`Streamer::stream_ticks`](struct.Streamer.html#method.stream_ticks) but from the recording
+   pub async fn stream_ticks(&self) -> Result<impl Stream<Item = Result<Tick>>> {
+      Ok(self.stream_data().await?.map(|data| data.map(Tick::new)))
+   }
+
+   /// Same as [`Streamer::stream_typed`](struct.Streamer.html#method.stream_typed) but from the recording
+   pub async fn stream_typed(&self) -> Result<impl Stream<Item = Result<TypedTick>>> {
+      Ok(self.stream_data().await?.map(|data| data.map(TypedTick::new)))
+   }
+
+   /// Reads the recording and streams the decoded frames
+   async fn stream_data(&self) -> Result<impl Stream<Item = Result<PricingData>>> {
+      let path = self.path.display().to_string();
+      let file = tokio::fs::File::open(&self.path).await.context(error::Recording { path: &path })?;
+
+      let state: (Lines<BufReader<tokio::fs::File>>, String, ReplaySpeed, Option<i64>) = (BufReader::new(file).lines(), path, self.speed, None);
+      Ok(stream::unfold(state, |(mut lines, path, speed, last)| async move {
+         let line = match lines.next().await? {
+            Ok(line) => line,
+            Err(source) => return Some((Err(error::InnerError::Recording { path: path.clone(), source }.into()), (lines, path, speed, last)))
+         };
+
+         let (received, frame) = match parse(&line) {
+            Ok(parsed) => parsed,
+            Err(error) => return Some((Err(error), (lines, path, speed, last)))
+         };
+
+         // keep the same gaps between frames as when they were received
+         if let Some(delay) = last.and_then(|last| speed.delay(received - last)) {
+            tokio::time::delay_for(delay).await;
+         }
+
+         Some((decode(frame), (lines, path, speed, Some(received))))
+      })
+      // heartbeats are recorded - but a streamer doesn't pass them on either
+      .filter(|data| future::ready(match data {
+         Ok(data) => !is_heartbeat(data),
+         Err(_) => true
+      })))
+   }
+}
+
+/// Splits a line of a recording into the time the frame was received and the frame
+fn parse(line: &str) -> Result<(i64, &str)> {
+   let mut parts = line.trim_end().splitn(2, ' ');
+   let received = parts.next().and_then(|received| received.parse().ok());
+   let frame = parts.next();
+
+   match (received, frame) {
+      (Some(received), Some(frame)) => Ok((received, frame)),
+      _ => Err(error::InnerError::StreamDecode { reason: format!("'{}' is not a recorded frame", line) }.into())
+   }
+}
@@ -0,0 +1,129 @@
+use futures::{ future, Stream, StreamExt };
+use market_finance::TradingSession;
+use std::collections::HashMap;
+
+use crate::Result;
+
+use super::Tick;
+
+/// The trading session a realtime update happened in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Session {
+   /// Trading before the regular market opens
+   PreMarket,
+
+   /// Trading while the regular market is open
+   Regular,
+
+   /// Trading right after the regular market closes
+   AfterHours,
+
+   /// Trading outside of the pre-market, regular and after hours sessions -
+   /// ie. overnight trading
+   ExtendedHours
+}
+impl From<Session> for TradingSession {
+   /// Extended hours become `TradingSession::Other` - since there's no such session in a quote
+   fn from(session: Session) -> TradingSession {
+      match session {
+         Session::PreMarket => TradingSession::PreMarket,
+         Session::Regular => TradingSession::Regular,
+         Session::AfterHours => TradingSession::AfterHours,
+         Session::ExtendedHours => TradingSession::Other
+      }
+   }
+}
+impl From<TradingSession> for Session {
+   /// `TradingSession::Other` becomes extended hours - since that's the only
+   /// session a streamed quote puts there
+   fn from(session: TradingSession) -> Session {
+      match session {
+         TradingSession::PreMarket => Session::PreMarket,
+         TradingSession::Regular => Session::Regular,
+         TradingSession::AfterHours => Session::AfterHours,
+         TradingSession::Other => Session::ExtendedHours
+      }
+   }
+}
+
+/// A symbol moving from one trading session to another - ie. from pre-market
+/// to regular trading
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionChange {
+   /// The symbol that changed sessions - ie. 'AAPL'
+   pub symbol: String,
+
+   /// When the first update in the new session happened in milliseconds since the epoch
+   pub timestamp: i64,
+
+   /// The session the symbol was in - or `None` for its first update
+   pub from: Option<Session>,
+
+   /// The session the symbol is in now
+   pub to: Session
+}
+
+/// Follows the trading session of every symbol in a stream of ticks and
+/// reports when it changes.
+///
+/// The first tick for a symbol is reported as a change from no session, so
+/// consumers learn the session a symbol starts in.  A tick that happened
+/// before the symbol's last change - ie. a late regular session trade
+/// reported after hours have started - doesn't change the session back.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use futures::{ future, StreamExt };
+/// use yahoo_finance::{ SessionTracker, Streamer };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let streamer = Streamer::new(vec!["AAPL", "MSFT"]);
+///    let ticks = streamer.stream_ticks().await.unwrap();
+///
+///    SessionTracker::new()
+///       .stream(ticks)
+///       .for_each(|change| {
+///          if let Ok(change) = change { println!("{} {:?} -> {:?}", change.symbol, change.from, change.to); }
+///          future::ready(())
+///       })
+///       .await;
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct SessionTracker {
+   symbols: HashMap<String, (Session, i64)>
+}
+impl SessionTracker {
+   /// Creates a tracker that hasn't seen any symbols yet
+   pub fn new() -> SessionTracker { SessionTracker::default() }
+
+   /// The session a symbol is currently in - if it's had a tick
+   pub fn session(&self, symbol: &str) -> Option<Session> {
+      self.symbols.get(symbol).map(|(session, _)| *session)
+   }
+
+   /// Adds a tick - returning the change if the symbol moved to another session
+   pub fn push(&mut self, tick: &Tick) -> Option<SessionChange> {
+      let from = match self.symbols.get(&tick.symbol) {
+         Some((session, _)) if *session == tick.session => return None,
+         Some((_, since)) if tick.timestamp < *since => return None,
+         Some((session, _)) => Some(*session),
+         None => None
+      };
+
+      self.symbols.insert(tick.symbol.clone(), (tick.session, tick.timestamp));
+      Some(SessionChange { symbol: tick.symbol.clone(), timestamp: tick.timestamp, from, to: tick.session })
+   }
+
+   /// Turns a stream of ticks - ie. from [`Streamer::stream_ticks`](struct.Streamer.html#method.stream_ticks) -
+   /// into a stream of session changes.  Errors in the ticks are passed along.
+   pub fn stream<S>(mut self, ticks: S) -> impl Stream<Item = Result<SessionChange>>
+   where S: Stream<Item = Result<Tick>> {
+      ticks.filter_map(move |tick| future::ready(match tick {
+         Ok(tick) => self.push(&tick).map(Ok),
+         Err(error) => Some(Err(error))
+      }))
+   }
+}
@@ -0,0 +1,50 @@
+use chrono::{ DateTime, Utc };
+use std::collections::HashMap;
+
+use crate::Result;
+use crate::yahoo::PricingData;
+
+use super::is_heartbeat;
+
+/// How a [`Streamer`](struct.Streamer.html) connection has been doing - counted
+/// across every connection the streamer has made, including reconnects.
+#[derive(Clone, Debug, Default)]
+pub struct StreamStats {
+   /// The number of frames received from Yahoo! - including heartbeats and
+   /// frames that couldn't be decoded
+   pub messages: u64,
+
+   /// The number of frames that couldn't be decoded
+   pub decode_failures: u64,
+
+   /// The number of heartbeats Yahoo! sent to show the connection is alive
+   pub heartbeats: u64,
+
+   /// The number of times the streamer reconnected
+   pub reconnects: u64,
+
+   /// When the last frame was received
+   pub last_message: Option<DateTime<Utc>>,
+
+   /// When the last update was received for each symbol
+   pub last_updates: HashMap<String, DateTime<Utc>>
+}
+impl StreamStats {
+   /// Counts a frame - returning whether it should be passed on, which
+   /// heartbeats aren't
+   pub(crate) fn observe(&mut self, data: &Result<PricingData>) -> bool {
+      let now = Utc::now();
+      self.messages += 1;
+      self.last_message = Some(now);
+
+      match data {
+         Err(_) => self.decode_failures += 1,
+         Ok(data) if is_heartbeat(data) => {
+            self.heartbeats += 1;
+            return false;
+         },
+         Ok(data) => { self.last_updates.insert(data.id.clone(), now); }
+      }
+      true
+   }
+}
@@ -0,0 +1,120 @@
+use serde::Serialize;
+use std::sync::{ Arc, Mutex };
+use tokio::sync::{ mpsc, watch };
+
+/// A subscription change as Yahoo! expects it - ie. `{"subscribe":["AAPL"]}`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Subs {
+   Subscribe(Vec<String>),
+   Unsubscribe(Vec<String>)
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+   symbols: Vec<String>,
+   listeners: Vec<mpsc::UnboundedSender<Subs>>
+}
+
+/// The symbols a streamer is subscribed to, shared between the streamer, its
+/// handles and the connections that are streaming.
+#[derive(Debug, Default)]
+pub(crate) struct Subscriptions {
+   inner: Mutex<Inner>
+}
+impl Subscriptions {
+   pub fn new(symbols: Vec<String>) -> Subscriptions {
+      let subs = Subscriptions::default();
+      subs.change(symbols, true);
+      subs
+   }
+
+   /// The symbols that are currently subscribed to
+   pub fn symbols(&self) -> Vec<String> {
+      self.inner.lock().unwrap().symbols.clone()
+   }
+
+   /// The symbols that are currently subscribed to along with a receiver for
+   /// every change made from now on
+   pub fn listen(&self) -> (Vec<String>, mpsc::UnboundedReceiver<Subs>) {
+      let (tx, rx) = mpsc::unbounded_channel();
+
+      let mut inner = self.inner.lock().unwrap();
+      inner.listeners.push(tx);
+      (inner.symbols.clone(), rx)
+   }
+
+   /// Adds or removes symbols - telling the listeners about the ones that
+   /// actually changed
+   fn change(&self, symbols: Vec<String>, subscribe: bool) {
+      let mut inner = self.inner.lock().unwrap();
+
+      let mut changed = Vec::new();
+      for symbol in symbols {
+         let position = inner.symbols.iter().position(|s| *s == symbol);
+         match (subscribe, position) {
+            (true, None) => { inner.symbols.push(symbol.clone()); changed.push(symbol); },
+            (false, Some(position)) => { inner.symbols.remove(position); changed.push(symbol); },
+            _ => {}
+         }
+      }
+      if changed.is_empty() { return; }
+
+      let message = if subscribe { Subs::Subscribe(changed) } else { Subs::Unsubscribe(changed) };
+      inner.listeners.retain(|listener| listener.send(message.clone()).is_ok());
+   }
+}
+
+/// Changes the symbols of a [`Streamer`](struct.Streamer.html), or stops it,
+/// while it is streaming.
+///
+/// Changes are sent to Yahoo! on every live connection and remembered so that
+/// later connections - including reconnects - subscribe to the current symbols.
+/// Handles are cheap to clone and can be moved to other tasks.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use futures::{ future, StreamExt };
+/// use yahoo_finance::Streamer;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let streamer = Streamer::new(vec!["AAPL"]);
+///    let handle = streamer.handle();
+///
+///    let quotes = streamer.stream().await.unwrap();
+///    handle.subscribe(&["MSFT", "GOOG"]);
+///    handle.unsubscribe(&["AAPL"]);
+///
+///    quotes.for_each(|quote| { println!("{:?}", quote); future::ready(()) }).await;
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamHandle {
+   subs: Arc<Subscriptions>,
+   shutdown: Arc<watch::Sender<bool>>
+}
+impl StreamHandle {
+   pub(crate) fn new(subs: Arc<Subscriptions>, shutdown: Arc<watch::Sender<bool>>) -> StreamHandle {
+      StreamHandle { subs, shutdown }
+   }
+
+   /// Starts streaming quotes for more symbols
+   pub fn subscribe(&self, symbols: &[&str]) {
+      self.subs.change(symbols.iter().map(|s| s.to_string()).collect(), true);
+   }
+
+   /// Stops streaming quotes for some symbols
+   pub fn unsubscribe(&self, symbols: &[&str]) {
+      self.subs.change(symbols.iter().map(|s| s.to_string()).collect(), false);
+   }
+
+   /// The symbols that are currently being streamed
+   pub fn symbols(&self) -> Vec<String> { self.subs.symbols() }
+
+   /// Same as [`Streamer::stop`](struct.Streamer.html#method.stop)
+   pub fn stop(&self) {
+      let _ = self.shutdown.broadcast(true);
+   }
+}
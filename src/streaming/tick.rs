@@ -0,0 +1,298 @@
+use chrono::{ DateTime, TimeZone, Utc };
+use market_finance::{ Quote, Timestamped };
+
+use crate::QuoteType;
+use crate::options::OptionKind;
+use crate::yahoo::{ PricingData, PricingData_OptionType, PricingData_QuoteType };
+
+use super::{ convert_session, Session };
+
+fn convert_quote_type(value: PricingData_QuoteType) -> Option<QuoteType> {
+   match value {
+      PricingData_QuoteType::NONE | PricingData_QuoteType::HEARTBEAT => None,
+      PricingData_QuoteType::EQUITY => Some(QuoteType::Equity),
+      PricingData_QuoteType::ETF => Some(QuoteType::Etf),
+      PricingData_QuoteType::INDEX => Some(QuoteType::Index),
+      PricingData_QuoteType::MUTUALFUND => Some(QuoteType::MutualFund),
+      PricingData_QuoteType::MONEYMARKET => Some(QuoteType::MoneyMarket),
+      PricingData_QuoteType::OPTION => Some(QuoteType::Option),
+      PricingData_QuoteType::CURRENCY => Some(QuoteType::Currency),
+      PricingData_QuoteType::CRYPTOCURRENCY => Some(QuoteType::Cryptocurrency),
+      PricingData_QuoteType::FUTURE => Some(QuoteType::Future),
+      PricingData_QuoteType::WARRANT => Some(QuoteType::Warrant),
+      PricingData_QuoteType::BOND => Some(QuoteType::Bond),
+      PricingData_QuoteType::COMMODITY => Some(QuoteType::Commodity),
+      _ => Some(QuoteType::Other)
+   }
+}
+
+/// Yahoo! leaves out values that are zero - so treat those as missing
+fn price(value: f32) -> Option<f64> { if value == 0.0 { None } else { Some(f64::from(value)) } }
+fn size(value: i64) -> Option<u64> { if value <= 0 { None } else { Some(value as u64) } }
+fn amount(value: f64) -> Option<f64> { if value == 0.0 { None } else { Some(value) } }
+fn text(value: String) -> Option<String> { if value.is_empty() { None } else { Some(value) } }
+
+/// Everything Yahoo! sends about a symbol in a single realtime update.
+///
+/// Yahoo! only sends the values that are set, so a tick often carries just
+/// the values that changed - a missing value means it wasn't in this update
+/// rather than that it's zero.
+#[derive(Clone, Debug)]
+pub struct Tick {
+   /// The symbol the update is for - ie. 'AAPL'
+   pub symbol: String,
+
+   /// When the update happened in milliseconds since the epoch
+   pub timestamp: i64,
+
+   /// The trading session the update happened in
+   pub session: Session,
+
+   /// The kind of security the symbol is
+   pub quote_type: Option<QuoteType>,
+
+   /// The latest price
+   pub price: f64,
+
+   /// The change in price since the previous close - Yahoo! leaves out a change
+   /// of zero, so this is also missing when the price is back at the close
+   pub change: Option<f64>,
+
+   /// The change in price since the previous close as a percentage
+   pub change_percent: Option<f64>,
+
+   /// The price the symbol opened at today
+   pub open: Option<f64>,
+
+   /// The highest price today
+   pub day_high: Option<f64>,
+
+   /// The lowest price today
+   pub day_low: Option<f64>,
+
+   /// The closing price of the previous trading day
+   pub previous_close: Option<f64>,
+
+   /// The number of shares (or contracts, coins, etc.) traded today
+   pub day_volume: Option<u64>,
+
+   /// The size of the last trade
+   pub last_size: Option<u64>,
+
+   /// The best bid
+   pub bid: Option<f64>,
+
+   /// The size of the best bid
+   pub bid_size: Option<u64>,
+
+   /// The best ask
+   pub ask: Option<f64>,
+
+   /// The size of the best ask
+   pub ask_size: Option<u64>,
+
+   /// The number of decimal places prices should be shown with
+   pub price_hint: Option<u64>,
+
+   /// The currency prices are in - ie. 'USD'
+   pub currency: Option<String>,
+
+   /// The exchange the symbol trades on - ie. 'NMS'
+   pub exchange: Option<String>,
+
+   /// A short name for the symbol - ie. 'Apple Inc.'
+   pub short_name: Option<String>,
+
+   /// The total value of the symbol
+   pub market_cap: Option<f64>,
+
+   /// The volume over the last 24 hours for cryptocurrencies
+   pub volume_24h: Option<u64>,
+
+   /// The volume over the last 24 hours in all currencies for cryptocurrencies
+   pub volume_all_currencies: Option<u64>,
+
+   /// The currency a cryptocurrency is quoted from - ie. 'BTC'
+   pub from_currency: Option<String>,
+
+   /// The market the last cryptocurrency trade happened on
+   pub last_market: Option<String>,
+
+   /// The number of coins in circulation for cryptocurrencies
+   pub circulating_supply: Option<f64>
+}
+impl Tick {
+   pub(crate) fn new(data: PricingData) -> Tick {
+      Tick {
+         session: convert_session(data.marketHours),
+         quote_type: convert_quote_type(data.quoteType),
+         timestamp: data.time,
+         price: f64::from(data.price),
+         change: price(data.change),
+         change_percent: price(data.changePercent),
+         open: price(data.openPrice),
+         day_high: price(data.dayHigh),
+         day_low: price(data.dayLow),
+         previous_close: price(data.previousClose),
+         day_volume: size(data.dayVolume),
+         last_size: size(data.lastSize),
+         bid: price(data.bid),
+         bid_size: size(data.bidSize),
+         ask: price(data.ask),
+         ask_size: size(data.askSize),
+         price_hint: size(data.priceHint),
+         market_cap: amount(data.marketcap),
+         volume_24h: size(data.vol_24hr),
+         volume_all_currencies: size(data.volAllCurrencies),
+         circulating_supply: amount(data.circulatingSupply),
+         currency: text(data.currency),
+         exchange: text(data.exchange),
+         short_name: text(data.shortName),
+         from_currency: text(data.fromcurrency),
+         last_market: text(data.lastMarket),
+         symbol: data.id
+      }
+   }
+
+   /// Cuts the tick down to a quote
+   pub fn quote(&self) -> Quote {
+      Quote {
+         symbol: self.symbol.clone(),
+         timestamp: self.timestamp,
+         session: self.session.into(),
+         price: self.price,
+         volume: self.day_volume.unwrap_or(0)
+      }
+   }
+}
+impl Timestamped for Tick {
+   /// Gets the timestamp in millisecond accuracy
+   fn timestamp_millis(&self) -> i64 { self.timestamp }
+}
+
+/// The option specific values of a realtime update
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionDetails {
+   /// The symbol the option is for - ie. 'AAPL'
+   pub underlying: Option<String>,
+
+   /// Whether the option is a call or a put - if it's known
+   pub kind: Option<OptionKind>,
+
+   /// The price the underlying can be bought or sold at
+   pub strike: Option<f64>,
+
+   /// When the option expires
+   pub expiration: Option<DateTime<Utc>>,
+
+   /// The number of contracts that are currently open
+   pub open_interest: Option<u64>,
+
+   /// Whether this is a mini option - ie. for 10 shares rather than 100
+   pub mini: bool
+}
+impl OptionDetails {
+   fn new(data: &PricingData) -> OptionDetails {
+      OptionDetails {
+         underlying: text(data.underlyingSymbol.clone()),
+         kind: match data.optionsType {
+            PricingData_OptionType::PUT => Some(OptionKind::Put),
+
+            // a call is the default - so it's also what we get when Yahoo!
+            // leaves the type out, and only the symbol can tell us
+            PricingData_OptionType::CALL => contract_kind(&data.id)
+         },
+         strike: price(data.strikePrice),
+         expiration: if data.expireDate == 0 { None } else { Utc.timestamp_opt(data.expireDate, 0).single() },
+         open_interest: size(data.openInterest),
+         mini: data.miniOption != 0
+      }
+   }
+}
+
+/// Reads the kind of option from a contract symbol - which ends with 'C' or 'P'
+/// and the strike price in 8 digits, ie. 'AAPL200918C00150000'
+fn contract_kind(symbol: &str) -> Option<OptionKind> {
+   let symbol = symbol.as_bytes();
+   if symbol.len() < 15 || !symbol[symbol.len() - 8..].iter().all(u8::is_ascii_digit) { return None; }
+
+   match symbol[symbol.len() - 9] {
+      b'C' => Some(OptionKind::Call),
+      b'P' => Some(OptionKind::Put),
+      _ => None
+   }
+}
+
+/// The cryptocurrency specific values of a realtime update
+#[derive(Clone, Debug, PartialEq)]
+pub struct CryptoDetails {
+   /// The currency the cryptocurrency is quoted from - ie. 'BTC'
+   pub from_currency: Option<String>,
+
+   /// The market the last trade happened on
+   pub last_market: Option<String>,
+
+   /// The volume over the last 24 hours
+   pub volume_24h: Option<u64>,
+
+   /// The volume over the last 24 hours in all currencies
+   pub volume_all_currencies: Option<u64>,
+
+   /// The number of coins in circulation
+   pub circulating_supply: Option<f64>,
+
+   /// The total value of the coins in circulation
+   pub market_cap: Option<f64>
+}
+impl CryptoDetails {
+   fn new(tick: &Tick) -> CryptoDetails {
+      CryptoDetails {
+         from_currency: tick.from_currency.clone(),
+         last_market: tick.last_market.clone(),
+         volume_24h: tick.volume_24h,
+         volume_all_currencies: tick.volume_all_currencies,
+         circulating_supply: tick.circulating_supply,
+         market_cap: tick.market_cap
+      }
+   }
+}
+
+/// A realtime update along with the values that only make sense for its kind
+/// of security
+#[derive(Clone, Debug)]
+pub enum TypedTick {
+   /// An update for an option contract
+   Option { tick: Tick, option: OptionDetails },
+
+   /// An update for a cryptocurrency
+   Crypto { tick: Tick, crypto: CryptoDetails },
+
+   /// An update for anything else - ie. equities, indexes, currencies, etc.
+   Other(Tick)
+}
+impl TypedTick {
+   pub(crate) fn new(data: PricingData) -> TypedTick {
+      match data.quoteType {
+         PricingData_QuoteType::OPTION => {
+            let option = OptionDetails::new(&data);
+            TypedTick::Option { tick: Tick::new(data), option }
+         },
+         PricingData_QuoteType::CRYPTOCURRENCY => {
+            let tick = Tick::new(data);
+            TypedTick::Crypto { crypto: CryptoDetails::new(&tick), tick }
+         },
+         _ => TypedTick::Other(Tick::new(data))
+      }
+   }
+
+   /// The values common to every kind of security
+   pub fn tick(&self) -> &Tick {
+      match self {
+         TypedTick::Option { tick, .. } | TypedTick::Crypto { tick, .. } | TypedTick::Other(tick) => tick
+      }
+   }
+}
+impl Timestamped for TypedTick {
+   /// Gets the timestamp in millisecond accuracy
+   fn timestamp_millis(&self) -> i64 { self.tick().timestamp }
+}
@@ -0,0 +1,228 @@
+//! A local stand-in for the Yahoo! streaming server so that code using a
+//! [`Streamer`](../struct.Streamer.html) can be tested without the network.
+//!
+//! The server plays a script for every connection it accepts, in order.  Once
+//! the scripts run out, connections are refused - which is handy for testing
+//! reconnects that give up.
+//!
+//! # Examples
+//!
+//! ```
+//! use futures::StreamExt;
+//! use yahoo_finance::Client;
+//! use yahoo_finance::test_support::{ FrameBuilder, MockStreamServer, Step };
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!    let server = MockStreamServer::start(vec![vec![
+//!       Step::Receive,
+//!       Step::Send(FrameBuilder::new("AAPL").price(150.0).build()),
+//!       Step::Close
+//!    ]]).await;
+//!
+//!    let client = Client::builder().streaming_url(&server.url()).build().unwrap();
+//!    let quotes: Vec<_> = client.streamer(vec!["AAPL"]).stream().await.unwrap().collect().await;
+//!
+//!    assert_eq!(150.0, quotes[0].as_ref().unwrap().price);
+//!    assert_eq!(vec![r#"{"subscribe":["AAPL"]}"#.to_string()], server.received());
+//! }
+//! ```
+
+use futures::{ SinkExt, StreamExt };
+use protobuf::Message as _;
+use std::collections::VecDeque;
+use std::sync::{ Arc, Mutex };
+use std::time::Duration;
+use tokio::net::{ TcpListener, TcpStream };
+use tokio_tungstenite::{ accept_async, tungstenite::protocol::Message, WebSocketStream };
+
+use crate::{ QuoteType, Session };
+use crate::yahoo::{ PricingData, PricingData_MarketHoursType, PricingData_QuoteType };
+
+/// Builds a base64 encoded `PricingData` frame like the ones Yahoo! sends.
+/// Values that aren't set are left out of the frame.
+#[derive(Clone, Debug)]
+pub struct FrameBuilder {
+   data: PricingData
+}
+impl FrameBuilder {
+   /// Starts a frame for a symbol - ie. 'AAPL'
+   pub fn new(symbol: &str) -> FrameBuilder {
+      let mut data = PricingData::new();
+      data.set_id(symbol.to_string());
+      FrameBuilder { data }
+   }
+
+   /// Starts a heartbeat frame - which Yahoo! sends to show the connection is alive
+   pub fn heartbeat() -> FrameBuilder {
+      let mut data = PricingData::new();
+      data.set_quoteType(PricingData_QuoteType::HEARTBEAT);
+      FrameBuilder { data }
+   }
+
+   /// Sets the latest price
+   pub fn price(mut self, price: f32) -> FrameBuilder {
+      self.data.set_price(price);
+      self
+   }
+
+   /// Sets the change in price since the previous close - as an amount and a percentage
+   pub fn change(mut self, change: f32, percent: f32) -> FrameBuilder {
+      self.data.set_change(change);
+      self.data.set_changePercent(percent);
+      self
+   }
+
+   /// Sets when the update happened in milliseconds since the epoch
+   pub fn time(mut self, time: i64) -> FrameBuilder {
+      self.data.set_time(time);
+      self
+   }
+
+   /// Sets the trading session the update happened in
+   pub fn session(mut self, session: Session) -> FrameBuilder {
+      self.data.set_marketHours(match session {
+         Session::PreMarket => PricingData_MarketHoursType::PRE_MARKET,
+         Session::Regular => PricingData_MarketHoursType::REGULAR_MARKET,
+         Session::AfterHours => PricingData_MarketHoursType::POST_MARKET,
+         Session::ExtendedHours => PricingData_MarketHoursType::EXTENDED_HOURS_MARKET
+      });
+      self
+   }
+
+   /// Sets the kind of security the symbol is
+   pub fn quote_type(mut self, quote_type: QuoteType) -> FrameBuilder {
+      self.data.set_quoteType(match quote_type {
+         QuoteType::Equity => PricingData_QuoteType::EQUITY,
+         QuoteType::Etf => PricingData_QuoteType::ETF,
+         QuoteType::Index => PricingData_QuoteType::INDEX,
+         QuoteType::MutualFund => PricingData_QuoteType::MUTUALFUND,
+         QuoteType::MoneyMarket => PricingData_QuoteType::MONEYMARKET,
+         QuoteType::Option => PricingData_QuoteType::OPTION,
+         QuoteType::Currency => PricingData_QuoteType::CURRENCY,
+         QuoteType::Cryptocurrency => PricingData_QuoteType::CRYPTOCURRENCY,
+         QuoteType::Future => PricingData_QuoteType::FUTURE,
+         QuoteType::Warrant => PricingData_QuoteType::WARRANT,
+         QuoteType::Bond => PricingData_QuoteType::BOND,
+         QuoteType::Commodity => PricingData_QuoteType::COMMODITY,
+         QuoteType::Other => PricingData_QuoteType::INDICATOR
+      });
+      self
+   }
+
+   /// Sets the number of shares traded today
+   pub fn day_volume(mut self, volume: i64) -> FrameBuilder {
+      self.data.set_dayVolume(volume);
+      self
+   }
+
+   /// Sets the best bid and its size
+   pub fn bid(mut self, bid: f32, size: i64) -> FrameBuilder {
+      self.data.set_bid(bid);
+      self.data.set_bidSize(size);
+      self
+   }
+
+   /// Sets the best ask and its size
+   pub fn ask(mut self, ask: f32, size: i64) -> FrameBuilder {
+      self.data.set_ask(ask);
+      self.data.set_askSize(size);
+      self
+   }
+
+   /// Encodes the frame
+   pub fn build(&self) -> String {
+      base64::encode(self.data.write_to_bytes().expect("a frame can always be encoded"))
+   }
+}
+
+/// Something the server does on a connection
+#[derive(Clone, Debug)]
+pub enum Step {
+   /// Waits for a message from the streamer - ie. a subscription or a pong
+   Receive,
+
+   /// Sends a text frame - usually one from a [`FrameBuilder`](struct.FrameBuilder.html)
+   Send(String),
+
+   /// Sends a ping
+   Ping,
+
+   /// Waits a while before the next step
+   Wait(Duration),
+
+   /// Closes the connection cleanly
+   Close,
+
+   /// Drops the connection without closing it - like a network failure
+   Drop
+}
+
+/// A local websocket server that follows a script for each connection
+pub struct MockStreamServer {
+   url: String,
+   received: Arc<Mutex<Vec<String>>>,
+   pongs: Arc<Mutex<usize>>
+}
+impl MockStreamServer {
+   /// Starts the server on a free local port - which has to be done within
+   /// the runtime the streamer will use
+   pub async fn start(scripts: Vec<Vec<Step>>) -> MockStreamServer {
+      let mut listener = TcpListener::bind("127.0.0.1:0").await.expect("a local port to listen on");
+      let url = format!("ws://{}", listener.local_addr().expect("the local address"));
+
+      let server = MockStreamServer { url, received: Arc::new(Mutex::new(Vec::new())), pongs: Arc::new(Mutex::new(0)) };
+      let received = server.received.clone();
+      let pongs = server.pongs.clone();
+
+      let mut scripts: VecDeque<Vec<Step>> = scripts.into();
+      tokio::spawn(async move {
+         while let Ok((stream, _)) = listener.accept().await {
+            // refuse the connection once we're out of scripts
+            let script = match scripts.pop_front() { Some(script) => script, None => continue };
+
+            if let Ok(socket) = accept_async(stream).await {
+               tokio::spawn(play(socket, script, received.clone(), pongs.clone()));
+            }
+         }
+      });
+
+      server
+   }
+
+   /// The URL to stream from - for [`ClientBuilder::streaming_url`](../struct.ClientBuilder.html#method.streaming_url)
+   pub fn url(&self) -> String { self.url.clone() }
+
+   /// The text messages the streamers have sent so far - ie. subscriptions
+   pub fn received(&self) -> Vec<String> { self.received.lock().unwrap().clone() }
+
+   /// The number of pongs the streamers have sent so far
+   pub fn pongs(&self) -> usize { *self.pongs.lock().unwrap() }
+}
+
+async fn play(mut socket: WebSocketStream<TcpStream>, script: Vec<Step>, received: Arc<Mutex<Vec<String>>>, pongs: Arc<Mutex<usize>>) {
+   for step in script {
+      let sent = match step {
+         Step::Receive => match socket.next().await {
+            Some(Ok(Message::Text(text))) => { received.lock().unwrap().push(text); Ok(()) },
+            Some(Ok(Message::Pong(_))) => { *pongs.lock().unwrap() += 1; Ok(()) },
+            _ => return
+         },
+         Step::Send(frame) => socket.send(Message::Text(frame)).await,
+         Step::Ping => socket.send(Message::Ping(b"ping".to_vec())).await,
+         Step::Wait(duration) => { tokio::time::delay_for(duration).await; Ok(()) },
+         Step::Close => { let _ = socket.close(None).await; return },
+         Step::Drop => return
+      };
+      if sent.is_err() { return; }
+   }
+
+   // keep the connection open so the streamer can be stopped
+   while let Some(Ok(message)) = socket.next().await {
+      match message {
+         Message::Text(text) => received.lock().unwrap().push(text),
+         Message::Pong(_) => *pongs.lock().unwrap() += 1,
+         _ => {}
+      }
+   }
+}
@@ -3,33 +3,71 @@ use chrono::{DateTime, Utc};
 use reqwest::Url;
 use serde::Deserialize;
 use snafu::{ ensure, OptionExt, ResultExt };
-use std::env;
+use std::collections::HashMap;
 
-use crate::{error, Interval, Result};
-
-const BASE_URL: &'static str = "https://query1.finance.yahoo.com/v8/finance/chart/";
+use crate::{error, Client, Interval, Result};
 
 /// Helper function to build up the main query URL
-fn build_query(symbol: &str) -> Result<Url> {
-   let base = env::var("TEST_URL").unwrap_or(BASE_URL.to_string());
-   Ok(Url::parse(&base).context(error::InternalURL { url: &base })?
-      .join(symbol).context(error::InternalURL { url: symbol })?)
+fn build_query(client: &Client, symbol: &str) -> Result<Url> {
+   Ok(client.chart_url().join(symbol).context(error::InternalURL { url: symbol })?)
 }
 
+/// Helper function to build up a query for a date range
+fn build_range_query(client: &Client, symbol: &str, interval: Interval, start: i64, end: i64) -> Result<Url> {
+   let mut lookup = build_query(client, symbol)?;
+   lookup.query_pairs_mut()
+      .append_pair("period1", &start.to_string())
+      .append_pair("period2", &end.to_string())
+      .append_pair("interval", &interval.to_string());
+
+   Ok(lookup)
+}
+
+ez_serde!(TradingPeriod {
+   timezone: String,
+
+   #[serde(with = "ts_seconds")]
+   start: DateTime<Utc>,
+
+   #[serde(with = "ts_seconds")]
+   end: DateTime<Utc>,
+
+   gmtoffset: i64
+});
+
+ez_serde!(TradingPeriods { pre: TradingPeriod, regular: TradingPeriod, post: TradingPeriod });
+
 ez_serde!(Meta {
    symbol: String,
 
+   currency: Option<String>,
+
+   exchange_name: Option<String>,
+
+   instrument_type: Option<String>,
+
    #[serde(with = "ts_seconds")]
    first_trade_date: DateTime<Utc>,
 
    #[serde(rename = "regularMarketPrice")]
-   current_price: f32,
+   current_price: f64,
 
    #[serde(rename = "chartPreviousClose")]
-   previous_close: f32
+   previous_close: f64,
+
+   exchange_timezone_name: Option<String>,
+
+   gmtoffset: Option<i64>,
+
+   price_hint: Option<i64>,
+
+   current_trading_period: Option<TradingPeriods>,
+
+   #[serde(default)]
+   valid_ranges: Vec<String>
 });
 
-ez_serde!(OHLCV {
+ez_serde!(Ohlcv {
    #[serde(rename = "open", default)]
    opens: Vec<Option<f64>>,
 
@@ -46,11 +84,33 @@ ez_serde!(OHLCV {
    volumes: Vec<Option<u64>>
 });
 
-ez_serde!(Indicators { #[serde(rename = "quote", default)] quotes: Vec<OHLCV> });
+ez_serde!(AdjClose { #[serde(rename = "adjclose", default)] adjcloses: Vec<Option<f64>> });
+
+ez_serde!(Indicators {
+   #[serde(rename = "quote", default)]
+   quotes: Vec<Ohlcv>,
+
+   #[serde(rename = "adjclose", default)]
+   adjcloses: Vec<AdjClose>
+});
+
+ez_serde!(Dividend { amount: f64, date: i64 });
+ez_serde!(Split { date: i64, numerator: f64, denominator: f64 });
+
+ez_serde!(Events {
+   #[serde(default)]
+   dividends: HashMap<String, Dividend>,
+
+   #[serde(default)]
+   splits: HashMap<String, Split>
+});
 
 ez_serde!(Data {
    meta: Meta,
 
+   #[serde(default)]
+   events: Option<Events>,
+
    #[serde(rename = "timestamp", default)]
    timestamps: Vec<i64>,
 
@@ -61,45 +121,64 @@ ez_serde!(Error {code: String, description: String });
 ez_serde!(Chart { result: Option<Vec<Data>>, error: Option<Error> });
 ez_serde!(Response { chart: Chart });
 
-async fn load(url: &Url) -> Result<Data> {
-   // make the call - we do not really expect this to fail.
-   // ie - we won't 404 if the symbol doesn't exist
-   let response = reqwest::get(url.clone()).await.context(error::RequestFailed)?;
-   ensure!(
-      response.status().is_success(),
-      error::CallFailed{ url: response.url().to_string(), status: response.status().as_u16() }
-   );
+// only the events of a chart - so that bars we don't need can't fail the call
+ez_serde!(EventsData { #[serde(default)] events: Option<Events> });
+ez_serde!(EventsChart { result: Option<Vec<EventsData>>, error: Option<Error> });
+ez_serde!(EventsResponse { chart: EventsChart });
 
-   let data = response.text().await.context(error::UnexpectedErrorRead { url: url.to_string() })?;
+async fn load(client: &Client, url: &Url) -> Result<Data> {
+   let data = client.fetch(url).await?;
    let chart = serde_json::from_str::<Response>(&data).context(error::BadData)?.chart;
+   first_result(chart.result, chart.error)
+}
 
-   if !chart.result.is_some() {
+/// Picks the result out of a chart - or fails with the error Yahoo! sent instead
+fn first_result<T>(result: Option<Vec<T>>, error: Option<Error>) -> Result<T> {
+   if result.is_none() {
       // no result so we'd better have an error
-      let err = chart.error.context(error::InternalLogic{ reason: "error block exists without values"})?;
+      let err = error.context(error::InternalLogic{ reason: "error block exists without values"})?;
       error::ChartFailed{ code: err.code, description: err.description }.fail()?;
    }
 
    // we have a result to process
-   let result = chart.result.context(error::UnexpectedErrorYahoo)?;
-   ensure!(result.len() > 0, error::UnexpectedErrorYahoo);
-   Ok(result[0].clone())
+   let result = result.context(error::UnexpectedErrorYahoo)?;
+   ensure!(!result.is_empty(), error::UnexpectedErrorYahoo);
+   Ok(result.into_iter().next().context(error::UnexpectedErrorYahoo)?)
+}
+
+pub async fn load_daily(client: &Client, symbol: &str, period: Interval) -> Result<Data> {
+   load_period(client, symbol, Interval::_1d, period).await
+}
+
+pub async fn load_daily_range(client: &Client, symbol: &str, start: i64, end: i64) -> Result<Data> {
+   load_range(client, symbol, Interval::_1d, start, end).await
 }
 
-pub async fn load_daily(symbol: &str, period: Interval) -> Result<Data> {
-   let mut lookup = build_query(symbol)?;
+pub async fn load_period(client: &Client, symbol: &str, interval: Interval, period: Interval) -> Result<Data> {
+   let mut lookup = build_query(client, symbol)?;
    lookup.query_pairs_mut()
       .append_pair("range", &period.to_string())
-      .append_pair("interval", "1d");
+      .append_pair("interval", &interval.to_string());
 
-   load(&lookup).await
+   load(client, &lookup).await
 }
 
-pub async fn load_daily_range(symbol: &str, start: i64, end: i64) -> Result<Data> {
-   let mut lookup = build_query(symbol)?;
-   lookup.query_pairs_mut()
-      .append_pair("period1", &start.to_string())
-      .append_pair("period2", &end.to_string())
-      .append_pair("interval", "1d");
+pub async fn load_range(client: &Client, symbol: &str, interval: Interval, start: i64, end: i64) -> Result<Data> {
+   load(client, &build_range_query(client, symbol, interval, start, end)?).await
+}
+
+pub async fn load_daily_range_with_events(client: &Client, symbol: &str, start: i64, end: i64) -> Result<Data> {
+   let mut lookup = build_range_query(client, symbol, Interval::_1d, start, end)?;
+   lookup.query_pairs_mut().append_pair("events", "div,split");
+
+   load(client, &lookup).await
+}
+
+pub async fn load_daily_range_events(client: &Client, symbol: &str, start: i64, end: i64) -> Result<Option<Events>> {
+   let mut lookup = build_range_query(client, symbol, Interval::_1d, start, end)?;
+   lookup.query_pairs_mut().append_pair("events", "div,split");
 
-   load(&lookup).await
+   let data = client.fetch(&lookup).await?;
+   let chart = serde_json::from_str::<EventsResponse>(&data).context(error::BadData)?.chart;
+   Ok(first_result(chart.result, chart.error)?.events)
 }
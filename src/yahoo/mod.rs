@@ -1,8 +1,18 @@
 mod chart;
-pub use chart::{load_daily, load_daily_range, Data};
+pub use chart::{load_daily, load_daily_range, load_daily_range_events, load_daily_range_with_events, load_period, load_range, Data, Events, Meta, TradingPeriod};
 
+mod options;
+pub use options::{load_options, Contract};
+
+mod quote;
+pub use quote::{load_quotes, QuoteResult};
+
+#[allow(clippy::all, renamed_and_removed_lints, unused_parens, elided_lifetimes_in_paths, mismatched_lifetime_syntaxes)]
 mod realtime;
-pub use realtime::{PricingData, PricingData_MarketHoursType};
+pub use realtime::{PricingData, PricingData_MarketHoursType, PricingData_OptionType, PricingData_QuoteType};
+
+mod search;
+pub use search::{search, Match};
 
 mod web_scraper;
 pub use web_scraper::{scrape, QuoteSummaryStore, CompanyProfile};
@@ -0,0 +1,72 @@
+use chrono::serde::ts_seconds;
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::Deserialize;
+use snafu::ResultExt;
+
+use crate::{ error, Client, Result };
+
+ez_serde!(Contract {
+   contract_symbol: String,
+   strike: f64,
+   currency: Option<String>,
+   last_price: Option<f64>,
+   change: Option<f64>,
+   percent_change: Option<f64>,
+   volume: Option<u64>,
+   open_interest: Option<u64>,
+   bid: Option<f64>,
+   ask: Option<f64>,
+
+   #[serde(with = "ts_seconds")]
+   expiration: DateTime<Utc>,
+
+   last_trade_date: Option<i64>,
+   implied_volatility: Option<f64>,
+
+   #[serde(default)]
+   in_the_money: bool
+});
+
+ez_serde!(Options {
+   #[serde(with = "ts_seconds")]
+   expiration_date: DateTime<Utc>,
+
+   #[serde(default)]
+   calls: Vec<Contract>,
+
+   #[serde(default)]
+   puts: Vec<Contract>
+});
+
+ez_serde!(OptionResult {
+   underlying_symbol: String,
+
+   #[serde(default)]
+   expiration_dates: Vec<i64>,
+
+   #[serde(default)]
+   options: Vec<Options>
+});
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(OptionChain { result: Option<Vec<OptionResult>>, error: Option<Error> });
+ez_serde!(Response { option_chain: OptionChain });
+
+/// Loads the options for a symbol - for the nearest expiration unless one is given.
+/// Nothing is returned when Yahoo! has no options for the symbol.
+pub async fn load_options(client: &Client, symbol: &str, expiration: Option<i64>) -> Result<Option<OptionResult>> {
+   let mut lookup: Url = client.options_url().join(symbol).context(error::InternalURL { url: symbol })?;
+   if let Some(expiration) = expiration {
+      lookup.query_pairs_mut().append_pair("date", &expiration.to_string());
+   }
+
+   let data = client.fetch(&lookup).await?;
+   let chain = serde_json::from_str::<Response>(&data).context(error::BadData)?.option_chain;
+
+   if let Some(err) = chain.error {
+      error::OptionsFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   Ok(chain.result.unwrap_or_default().into_iter().next())
+}
@@ -0,0 +1,48 @@
+use serde::Deserialize;
+use snafu::{ OptionExt, ResultExt };
+
+use crate::{ error, Client, Result };
+
+ez_serde!(QuoteResult {
+   symbol: String,
+   short_name: Option<String>,
+   long_name: Option<String>,
+   quote_type: Option<String>,
+   currency: Option<String>,
+   exchange: Option<String>,
+   market_state: Option<String>,
+   regular_market_time: Option<i64>,
+   regular_market_price: Option<f64>,
+   regular_market_change: Option<f64>,
+   regular_market_change_percent: Option<f64>,
+   regular_market_open: Option<f64>,
+   regular_market_previous_close: Option<f64>,
+   regular_market_day_high: Option<f64>,
+   regular_market_day_low: Option<f64>,
+   regular_market_volume: Option<u64>,
+   bid: Option<f64>,
+   bid_size: Option<u64>,
+   ask: Option<f64>,
+   ask_size: Option<u64>,
+   fifty_two_week_high: Option<f64>,
+   fifty_two_week_low: Option<f64>,
+   market_cap: Option<u64>
+});
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(QuoteResponse { result: Option<Vec<QuoteResult>>, error: Option<Error> });
+ez_serde!(Response { quote_response: QuoteResponse });
+
+pub async fn load_quotes(client: &Client, symbols: &[&str]) -> Result<Vec<QuoteResult>> {
+   let mut lookup = client.quote_url().clone();
+   lookup.query_pairs_mut().append_pair("symbols", &symbols.join(","));
+
+   let data = client.fetch(&lookup).await?;
+   let response = serde_json::from_str::<Response>(&data).context(error::BadData)?.quote_response;
+
+   if let Some(err) = response.error {
+      error::QuoteFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   Ok(response.result.context(error::UnexpectedErrorYahoo)?)
+}
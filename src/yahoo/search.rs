@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use snafu::ResultExt;
+
+use crate::{ error, Client, Result };
+
+ez_serde!(Match {
+   symbol: String,
+   #[serde(rename = "shortname")] short_name: Option<String>,
+   #[serde(rename = "longname")] long_name: Option<String>,
+   exchange: Option<String>,
+   #[serde(rename = "exchDisp")] exchange_display: Option<String>,
+   quote_type: Option<String>,
+   score: Option<f64>
+});
+
+ez_serde!(Response { #[serde(default)] quotes: Vec<Match> });
+
+pub async fn search(client: &Client, query: &str, count: usize) -> Result<Vec<Match>> {
+   let mut lookup = client.search_url().clone();
+   lookup.query_pairs_mut()
+      .append_pair("q", query)
+      .append_pair("quotesCount", &count.to_string())
+      .append_pair("newsCount", "0");
+
+   let data = client.fetch(&lookup).await?;
+   Ok(serde_json::from_str::<Response>(&data).context(error::BadData)?.quotes)
+}
@@ -1,14 +1,11 @@
 use reqwest::Url;
 use serde::Deserialize;
-use snafu::{ ensure, OptionExt, ResultExt };
-use std::env;
+use snafu::{ OptionExt, ResultExt };
 use std::io::{ BufRead, Cursor };
 
-use crate::{ error, Result };
+use crate::{ error, Client, Result };
 
-const DATA_VAR: &'static str = "root.App.main";
-
-const BASE_URL: &'static str = "https://finance.yahoo.com";
+const DATA_VAR: &str = "root.App.main";
 
 ez_serde!(QuoteType {
    #[serde(rename = "longName")] name: String,
@@ -49,32 +46,23 @@ ez_serde!(Dispatcher { stores: Stores });
 ez_serde!(Context { dispatcher: Dispatcher });
 ez_serde!(Response { context: Context });
 
-pub async fn scrape<'a>(symbol: &'a str) -> Result<Stores> {
+pub async fn scrape(client: &Client, symbol: &str) -> Result<Stores> {
    // construct the lookup URL - encoding it so we're safe
-   let base = format!("{}/quote/{}", env::var("TEST_URL").unwrap_or(BASE_URL.to_string()), symbol);
+   let path = format!("quote/{}", symbol);
 
-   let mut url = Url::parse(base.as_str()).context(error::InternalURL { url: base })?;
+   let mut url: Url = client.profile_url().join(&path).context(error::InternalURL { url: path })?;
    url.query_pairs_mut().append_pair("p", symbol);
 
-   // make the call - we do not really expect this to fail.
-   // ie - we won't 404 if the symbol doesn't exist
-   let response = reqwest::get(url.clone()).await.context(error::RequestFailed)?;
-   ensure!(
-      response.status().is_success(),
-      error::CallFailed{ url: response.url().to_string(), status: response.status().as_u16() }
-   );
-
-   let line = Cursor::new(response.text().await.context(error::UnexpectedErrorRead { url: url.clone().to_string() })?)
+   let line = Cursor::new(client.fetch(&url).await?)
       .lines()
       .map(|line| line.unwrap())
-      .filter(|line| line.trim().starts_with(DATA_VAR))
-      .next()
+      .find(|line| line.trim().starts_with(DATA_VAR))
       .context(error::MissingData { reason: "no quote data" })?;
    
    let data = line
       .trim()
       .trim_start_matches(DATA_VAR)
-      .trim_start_matches(|c| c == ' ' || c == '=')
+      .trim_start_matches([' ', '='])
       .trim_end_matches(';');
 
    let response = serde_json::from_str::<Response>(data).context(error::BadData)?;
@@ -0,0 +1,204 @@
+use futures::{stream, StreamExt};
+use std::time::Duration;
+use tokio_test::block_on;
+use yahoo_finance::{BarAggregator, Quote, Result, Session, TradingSession};
+
+fn quote(symbol: &str, seconds: i64, price: f64, volume: u64) -> Quote {
+   Quote { symbol: symbol.to_string(), timestamp: seconds * 1_000, session: TradingSession::Regular, price, volume }
+}
+
+#[test]
+fn bars_ohlc() {
+   //! Ensure that quotes within an interval are combined into a single bar
+
+   // GIVEN - a minute aggregator
+   let mut aggregator = BarAggregator::new(Duration::from_secs(60));
+
+   // WHEN - we push quotes for one minute and then the next
+   assert!(aggregator.push(&quote("AAPL", 60, 10.0, 1000)).is_empty());
+   assert!(aggregator.push(&quote("AAPL", 70, 12.0, 1100)).is_empty());
+   assert!(aggregator.push(&quote("AAPL", 80, 9.0, 1150)).is_empty());
+   assert!(aggregator.push(&quote("AAPL", 119, 11.0, 1200)).is_empty());
+   let bars = aggregator.push(&quote("AAPL", 120, 11.5, 1300));
+
+   // THEN - the first minute is complete
+   assert_eq!(1, bars.len());
+   let bar = &bars[0];
+   assert!(bar.complete);
+   assert_eq!("AAPL", bar.symbol);
+   assert_eq!(60_000, bar.bar.timestamp);
+   assert_eq!(10.0, bar.bar.open);
+   assert_eq!(12.0, bar.bar.high);
+   assert_eq!(9.0, bar.bar.low);
+   assert_eq!(11.0, bar.bar.close);
+
+   // AND - the volume is what traded after the first quote
+   assert_eq!(Some(200), bar.bar.volume);
+
+   // AND - the next minute picks up the volume since the last quote
+   let bars = aggregator.flush();
+   assert_eq!(1, bars.len());
+   assert_eq!(120_000, bars[0].bar.timestamp);
+   assert_eq!(Some(100), bars[0].bar.volume);
+}
+
+#[test]
+fn bars_per_symbol() {
+   //! Ensure that every symbol gets its own bars
+
+   // GIVEN - a minute aggregator
+   let mut aggregator = BarAggregator::new(Duration::from_secs(60));
+
+   // WHEN - we push interleaved quotes for two symbols
+   aggregator.push(&quote("AAPL", 0, 10.0, 100));
+   aggregator.push(&quote("MSFT", 10, 20.0, 500));
+   aggregator.push(&quote("AAPL", 20, 11.0, 150));
+   let msft = aggregator.push(&quote("MSFT", 70, 21.0, 600));
+
+   // THEN - a later quote for one symbol only completes its own bar
+   assert_eq!(1, msft.len());
+   assert_eq!("MSFT", msft[0].symbol);
+   assert_eq!(20.0, msft[0].bar.close);
+
+   // AND - the other symbol's bar is still open
+   let bars = aggregator.flush();
+   assert_eq!(2, bars.len());
+   assert_eq!("AAPL", bars[0].symbol);
+   assert_eq!(11.0, bars[0].bar.close);
+   assert_eq!(Some(50), bars[0].bar.volume);
+}
+
+#[test]
+fn bars_volume_reset() {
+   //! Ensure that a new day's volume isn't treated as negative volume
+
+   // GIVEN - a minute aggregator that has seen a full day of volume
+   let mut aggregator = BarAggregator::new(Duration::from_secs(60));
+   aggregator.push(&quote("AAPL", 0, 10.0, 5000));
+
+   // WHEN - the day volume starts over
+   aggregator.push(&quote("AAPL", 86_400, 10.0, 300));
+   let bars = aggregator.flush();
+
+   // THEN - the new day's volume is used as is
+   assert_eq!(Some(300), bars[0].bar.volume);
+}
+
+#[test]
+fn bars_sessions() {
+   //! Ensure that quotes outside of the wanted sessions are left out
+
+   // GIVEN - an aggregator for the regular session only
+   let mut aggregator = BarAggregator::new(Duration::from_secs(60)).sessions(&[Session::Regular]);
+
+   // WHEN - we push pre-market quotes followed by a regular one
+   let mut pre = quote("AAPL", 0, 50.0, 100);
+   pre.session = TradingSession::PreMarket;
+   aggregator.push(&pre);
+   pre.timestamp = 10_000;
+   pre.volume = 400;
+   assert!(aggregator.push(&pre).is_empty());
+   aggregator.push(&quote("AAPL", 20, 10.0, 450));
+   let bars = aggregator.flush();
+
+   // THEN - only the regular quote makes it into the bar
+   assert_eq!(1, bars.len());
+   assert_eq!(10.0, bars[0].bar.open);
+   assert_eq!(10.0, bars[0].bar.high);
+
+   // AND - the pre-market volume isn't counted
+   assert_eq!(Some(50), bars[0].bar.volume);
+}
+
+#[test]
+fn bars_extended_hours() {
+   //! Ensure that extended hours quotes can be told apart from the other sessions
+
+   // GIVEN - an aggregator for extended hours only
+   let mut aggregator = BarAggregator::new(Duration::from_secs(60)).sessions(&[Session::ExtendedHours]);
+
+   // WHEN - we push an after hours quote followed by an overnight one
+   let mut after = quote("AAPL", 0, 50.0, 100);
+   after.session = TradingSession::AfterHours;
+   aggregator.push(&after);
+   let mut overnight = quote("AAPL", 10, 51.0, 150);
+   overnight.session = TradingSession::Other;
+   aggregator.push(&overnight);
+   let bars = aggregator.flush();
+
+   // THEN - only the overnight quote makes it into the bar
+   assert_eq!(1, bars.len());
+   assert_eq!(51.0, bars[0].bar.open);
+   assert_eq!(Some(50), bars[0].bar.volume);
+}
+
+#[test]
+fn bars_late_quote() {
+   //! Ensure that a late quote doesn't add its volume to a newer bar
+
+   // GIVEN - a minute aggregator that has moved on to the second minute
+   let mut aggregator = BarAggregator::new(Duration::from_secs(60));
+   aggregator.push(&quote("AAPL", 0, 10.0, 100));
+   aggregator.push(&quote("AAPL", 60, 11.0, 150));
+
+   // WHEN - a quote for the first minute arrives late, followed by an on time one
+   assert!(aggregator.push(&quote("AAPL", 50, 9.0, 170)).is_empty());
+   aggregator.push(&quote("AAPL", 70, 12.0, 200));
+   let bars = aggregator.flush();
+
+   // THEN - the second minute only has its own prices and volume
+   assert_eq!(1, bars.len());
+   assert_eq!(60_000, bars[0].bar.timestamp);
+   assert_eq!(11.0, bars[0].bar.low);
+   assert_eq!(Some(80), bars[0].bar.volume);
+}
+
+#[test]
+fn bars_partial() {
+   //! Ensure that partial bars are emitted when asked for
+
+   // GIVEN - an aggregator with partial bars
+   let mut aggregator = BarAggregator::new(Duration::from_secs(60)).partial(true);
+
+   // WHEN - we push quotes
+   let first = aggregator.push(&quote("AAPL", 0, 10.0, 100));
+   let second = aggregator.push(&quote("AAPL", 10, 11.0, 120));
+   let third = aggregator.push(&quote("AAPL", 60, 12.0, 130));
+
+   // THEN - every quote emits the bar so far
+   assert_eq!(1, first.len());
+   assert!(!first[0].complete);
+   assert_eq!(1, second.len());
+   assert_eq!(11.0, second[0].bar.high);
+
+   // AND - moving to a new interval emits the complete bar before the new partial one
+   assert_eq!(2, third.len());
+   assert!(third[0].complete);
+   assert_eq!(0, third[0].bar.timestamp);
+   assert!(!third[1].complete);
+   assert_eq!(60_000, third[1].bar.timestamp);
+}
+
+#[test]
+fn bars_stream() {
+   //! Ensure that a stream of quotes becomes a stream of bars
+
+   // GIVEN - a stream of quotes
+   let quotes: Vec<Result<Quote>> = vec![
+      Ok(quote("AAPL", 0, 10.0, 100)),
+      Ok(quote("AAPL", 30, 11.0, 110)),
+      Ok(quote("AAPL", 60, 12.0, 120))
+   ];
+
+   // WHEN - we aggregate it
+   let bars: Vec<_> = block_on(BarAggregator::new(Duration::from_secs(60)).stream(stream::iter(quotes)).collect());
+
+   // THEN - we get the complete bars, including the one left when the quotes end
+   assert_eq!(2, bars.len());
+   let first = bars[0].as_ref().unwrap();
+   assert_eq!(0, first.bar.timestamp);
+   assert_eq!(11.0, first.bar.close);
+   let last = bars[1].as_ref().unwrap();
+   assert_eq!(60_000, last.bar.timestamp);
+   assert!(last.complete);
+}
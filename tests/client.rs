@@ -0,0 +1,262 @@
+use mockito::{mock, Mock};
+use std::fs;
+use std::time::Duration;
+use tokio_test::block_on;
+use yahoo_finance::{history, Client, Interval, Profile, RateLimitMode, RateLimiter, RetryPolicy};
+
+fn client(max_attempts: u32) -> Client {
+   // Tell the actual code to use a test URL rather than the live one - and to retry quickly
+   Client::builder()
+      .chart_url(&mockito::server_url())
+      .retry(RetryPolicy { max_attempts, initial_backoff: Duration::from_millis(1), jitter: false, ..RetryPolicy::default() })
+      .build()
+      .unwrap()
+}
+
+fn limited_client(limiter: RateLimiter) -> Client {
+   Client::builder().chart_url(&mockito::server_url()).rate_limit(limiter).build().unwrap()
+}
+
+fn failing_mock(symbol: &str, status: usize) -> Mock {
+   mock("GET", format!("/{symbol}?range={r}&interval={i}", symbol=symbol, r=Interval::_6mo, i=Interval::_1d).as_str())
+      .with_status(status)
+}
+
+#[test]
+fn client_new() {
+   //! Ensure that a default client can be created
+
+   // WHEN - we create a client with the default configuration
+   let client = Client::new();
+
+   // THEN - it works
+   assert!(client.is_ok());
+}
+
+#[test]
+fn client_endpoints() {
+   //! Ensure that calls go to the endpoints the client was configured with
+
+   // GIVEN - valid responses on a test URL
+   let chart = fs::read_to_string("tests/history_data/aapl.json").unwrap();
+   let profile = fs::read_to_string("tests/profile_data/aapl.html").unwrap();
+   let _chart = mock("GET", format!("/AAPL?range={r}&interval={i}", r=Interval::_5d, i=Interval::_1d).as_str()).with_body(&chart).create();
+   let _profile = mock("GET", "/quote/AAPL?p=AAPL").with_body(&profile).create();
+
+   // WHEN - we load them through a client
+   let client = Client::builder().chart_url(&mockito::server_url()).profile_url(&mockito::server_url()).build().unwrap();
+   let bars = block_on(client.history().retrieve_interval("AAPL", Interval::_5d)).unwrap();
+   let profile = block_on(client.profile("AAPL")).unwrap();
+
+   // THEN - we get the data from the test URL
+   assert_eq!(5, bars.len());
+   assert!(matches!(profile, Profile::Company(_)));
+}
+
+#[test]
+fn retry_transient_failure() {
+   //! Ensure that transient failures are retried until we run out of attempts
+
+   // GIVEN - Yahoo! is having trouble
+   let symbol = "AAPL";
+   let _m = failing_mock(symbol, 503).expect(3).create();
+
+   // WHEN - we load the data
+   let err = block_on(client(3).history().retrieve(symbol)).unwrap_err();
+
+   // THEN - every attempt is made and reported
+   _m.assert();
+   assert_eq!(3, err.attempts());
+   assert!(format!("{:?}", err).contains("RetriesExhausted"));
+   assert!(err.is_retryable());
+}
+
+#[test]
+fn retry_fatal_failure() {
+   //! Ensure that failures which won't go away are not retried
+
+   // GIVEN - a call that can never succeed
+   let symbol = "QQQ";
+   let _m = failing_mock(symbol, 404).expect(1).create();
+
+   // WHEN - we load the data
+   let err = block_on(client(3).history().retrieve(symbol)).unwrap_err();
+
+   // THEN - we only try once
+   _m.assert();
+   assert_eq!(1, err.attempts());
+   assert!(format!("{:?}", err).contains("CallFailed"));
+   assert!(!err.is_retryable());
+}
+
+#[test]
+fn retry_custom_statuses() {
+   //! Ensure that errors are judged by the retry policy of the client that made the call
+
+   // GIVEN - a client that doesn't retry being throttled
+   let symbol = "TSLA";
+   let _m = failing_mock(symbol, 429).expect(1).create();
+   let client = Client::builder()
+      .chart_url(&mockito::server_url())
+      .retry(RetryPolicy { max_attempts: 3, retryable_statuses: vec![503], ..RetryPolicy::default() })
+      .build()
+      .unwrap();
+
+   // WHEN - Yahoo! throttles us
+   let err = block_on(client.history().retrieve(symbol)).unwrap_err();
+
+   // THEN - the error agrees with the client that it isn't worth retrying
+   _m.assert();
+   assert_eq!(1, err.attempts());
+   assert!(!err.is_retryable());
+}
+
+#[test]
+fn retry_honors_retry_after() {
+   //! Ensure that Yahoo! telling us to back off is honored
+
+   // GIVEN - Yahoo! is throttling us for a second
+   let symbol = "MSFT";
+   let _m = failing_mock(symbol, 429).with_header("retry-after", "1").expect(2).create();
+
+   // WHEN - we load the data
+   let start = std::time::Instant::now();
+   let err = block_on(client(2).history().retrieve(symbol)).unwrap_err();
+
+   // THEN - we waited as long as we were asked to
+   _m.assert();
+   assert_eq!(2, err.attempts());
+   assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[test]
+fn retry_caps_retry_after() {
+   //! Ensure that Yahoo! can't make us wait longer than the policy allows
+
+   // GIVEN - Yahoo! is asking us to back off for an hour
+   let symbol = "GOOG";
+   let _m = failing_mock(symbol, 503).with_header("retry-after", "3600").expect(2).create();
+   let client = Client::builder()
+      .chart_url(&mockito::server_url())
+      .retry(RetryPolicy { max_attempts: 2, max_backoff: Duration::from_millis(10), ..RetryPolicy::default() })
+      .build()
+      .unwrap();
+
+   // WHEN - we load the data
+   let start = std::time::Instant::now();
+   let err = block_on(client.history().retrieve(symbol)).unwrap_err();
+
+   // THEN - we only waited as long as the policy's longest backoff
+   _m.assert();
+   assert_eq!(2, err.attempts());
+   assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn retry_after_ignored_when_fatal() {
+   //! Ensure that a Retry-After on a failure we won't retry doesn't make us wait
+
+   // GIVEN - a call that can never succeed, but asks us to come back later
+   let symbol = "AMZN";
+   let _m = failing_mock(symbol, 404).with_header("retry-after", "3600").expect(1).create();
+
+   // WHEN - we load the data
+   let start = std::time::Instant::now();
+   let err = block_on(client(3).history().retrieve(symbol)).unwrap_err();
+
+   // THEN - we fail straight away
+   _m.assert();
+   assert_eq!(1, err.attempts());
+   assert!(!err.is_retryable());
+   assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn rate_limit_fail_fast() {
+   //! Ensure that calls over the limit fail immediately when asked to
+
+   // GIVEN - a limit of one call with no room to burst
+   let symbol = "IBM";
+   let _m = failing_mock(symbol, 200).with_body("{}").expect(1).create();
+   let client = limited_client(RateLimiter::new(0.1, 1).mode(RateLimitMode::FailFast));
+
+   // WHEN - we make two calls
+   let _ = block_on(client.history().retrieve(symbol));
+   let err = block_on(client.history().retrieve(symbol)).unwrap_err();
+
+   // THEN - only the first call reaches Yahoo!
+   _m.assert();
+   assert!(format!("{:?}", err).contains("RateLimited"));
+}
+
+#[test]
+fn rate_limit_wait() {
+   //! Ensure that calls over the limit wait for their turn
+
+   // GIVEN - a limit of 10 calls a second with no room to burst
+   let limiter = RateLimiter::new(10.0, 1);
+
+   // WHEN - we take 4 slots
+   let start = std::time::Instant::now();
+   for _ in 0..4 { block_on(limiter.acquire()).unwrap(); }
+
+   // THEN - we had to wait for 3 of them
+   assert!(start.elapsed() >= Duration::from_millis(290));
+   assert!(!limiter.try_acquire());
+}
+
+#[test]
+fn rate_limit_shared() {
+   //! Ensure that clones of a limiter share the same limit
+
+   // GIVEN - a limiter with a burst of 2 shared by two clones
+   let limiter = RateLimiter::new(0.1, 2);
+   let other = limiter.clone();
+
+   // WHEN - both take a slot
+   assert!(limiter.try_acquire());
+   assert!(other.try_acquire());
+
+   // THEN - there is nothing left for either
+   assert!(!limiter.try_acquire());
+   assert!(!other.try_acquire());
+}
+
+#[test]
+fn rate_limit_cancelled() {
+   //! Ensure that a caller who gives up waiting hands its slot back
+
+   // GIVEN - a limit of 10 calls a second with no room to burst - that's been used up
+   let limiter = RateLimiter::new(10.0, 1);
+   block_on(limiter.acquire()).unwrap();
+
+   // WHEN - a caller gives up waiting for the next slot
+   let timeout = block_on(async { tokio::time::timeout(Duration::from_millis(1), limiter.acquire()).await });
+   assert!(timeout.is_err());
+
+   // THEN - the next slot is free once it's been earned
+   std::thread::sleep(Duration::from_millis(150));
+   assert!(limiter.try_acquire());
+}
+
+#[test]
+fn set_default() {
+   //! Ensure that the free functions use an installed client - including its rate limit
+
+   // GIVEN - a client with a limit of one call and no room to burst
+   let symbol = "NFLX";
+   let _m = failing_mock(symbol, 200).with_body("{}").expect(1).create();
+   let client = limited_client(RateLimiter::new(0.1, 1).mode(RateLimitMode::FailFast));
+
+   // WHEN - we install it and make two calls through the free functions
+   assert!(Client::set_default(client));
+   let _ = block_on(history::retrieve(symbol));
+   let err = block_on(history::retrieve(symbol)).unwrap_err();
+
+   // THEN - only the first call reaches Yahoo!
+   _m.assert();
+   assert!(format!("{:?}", err).contains("RateLimited"));
+
+   // AND - the default can't be changed once it's in use
+   assert!(!Client::set_default(Client::new().unwrap()));
+}
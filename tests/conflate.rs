@@ -0,0 +1,184 @@
+use futures::StreamExt;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_test::block_on;
+use yahoo_finance::test_support::{FrameBuilder, MockStreamServer, Step};
+use yahoo_finance::{Client, Conflator, Quote, Replay, ReplaySpeed, Result, TradingSession};
+
+fn quote(symbol: &str, seconds: i64, price: f64) -> Quote {
+   Quote { symbol: symbol.to_string(), timestamp: seconds * 1_000, session: TradingSession::Regular, price, volume: 100 }
+}
+
+#[test]
+fn conflate_latest() {
+   //! Ensure that only the latest update for each symbol is kept
+
+   // GIVEN - a conflator
+   let mut conflator = Conflator::new(Duration::from_millis(250));
+
+   // WHEN - several updates arrive for two symbols within a window
+   conflator.push(quote("AAPL", 1, 10.0));
+   conflator.push(quote("MSFT", 2, 20.0));
+   conflator.push(quote("AAPL", 3, 11.0));
+   conflator.push(quote("AAPL", 4, 12.0));
+   let quotes = conflator.flush();
+
+   // THEN - we get the latest for each symbol in the order they first arrived
+   assert_eq!(2, quotes.len());
+   assert_eq!("AAPL", quotes[0].symbol);
+   assert_eq!(12.0, quotes[0].price);
+   assert_eq!("MSFT", quotes[1].symbol);
+
+   // AND - an empty window has nothing
+   assert!(conflator.flush().is_empty());
+}
+
+#[test]
+fn conflate_dedupe() {
+   //! Ensure that updates with nothing new can be dropped
+
+   // GIVEN - a conflator that drops duplicates
+   let mut conflator = Conflator::new(Duration::from_millis(250)).dedupe(true);
+   conflator.push(quote("AAPL", 1, 10.0));
+   assert_eq!(1, conflator.flush().len());
+
+   // WHEN - the next window only has the same price at a later time
+   conflator.push(quote("AAPL", 2, 10.0));
+   let unchanged = conflator.flush();
+
+   // THEN - it's dropped
+   assert!(unchanged.is_empty());
+
+   // AND - a change gets through
+   conflator.push(quote("AAPL", 3, 10.5));
+   assert_eq!(1, conflator.flush().len());
+}
+
+#[test]
+fn conflate_duplicates_kept() {
+   //! Ensure that duplicates are kept unless asked otherwise
+
+   // GIVEN - a conflator
+   let mut conflator = Conflator::new(Duration::from_millis(250));
+   conflator.push(quote("AAPL", 1, 10.0));
+   conflator.flush();
+
+   // WHEN - the next window has the same price
+   conflator.push(quote("AAPL", 2, 10.0));
+
+   // THEN - it's passed on
+   assert_eq!(1, conflator.flush().len());
+}
+
+#[test]
+fn conflate_ticks_merged() {
+   //! Ensure that values sent earlier in a window aren't lost
+
+   block_on(async {
+      // GIVEN - a server that sends the bid and then only a new price
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive,
+         Step::Send(FrameBuilder::new("AAPL").price(150.0).bid(149.5, 100).build()),
+         Step::Send(FrameBuilder::new("AAPL").price(150.5).build()),
+         Step::Close
+      ]]).await;
+      let streamer = Client::builder().streaming_url(&server.url()).build().unwrap().streamer(vec!["AAPL"]);
+
+      // WHEN - we conflate the ticks
+      let ticks = streamer.stream_ticks().await.unwrap();
+      let ticks: Vec<_> = Conflator::new(Duration::from_secs(10)).stream(ticks).collect().await;
+
+      // THEN - the tick has the latest price and the earlier bid
+      assert_eq!(1, ticks.len());
+      let tick = ticks[0].as_ref().unwrap();
+      assert_eq!(150.5, tick.price);
+      assert_eq!(Some(149.5), tick.bid);
+      assert_eq!(Some(100), tick.bid_size);
+   });
+}
+
+#[test]
+fn conflate_ticks_missing_change() {
+   //! Ensure that a change isn't lost when a later tick in the window leaves it out
+
+   block_on(async {
+      // GIVEN - a server that sends a change and then a tick without one
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive,
+         Step::Send(FrameBuilder::new("AAPL").price(150.0).change(1.5, 1.01).build()),
+         Step::Send(FrameBuilder::new("AAPL").price(150.5).build()),
+         Step::Close
+      ]]).await;
+      let streamer = Client::builder().streaming_url(&server.url()).build().unwrap().streamer(vec!["AAPL"]);
+
+      // WHEN - we conflate the ticks
+      let ticks = streamer.stream_ticks().await.unwrap();
+      let ticks: Vec<_> = Conflator::new(Duration::from_secs(10)).stream(ticks).collect().await;
+
+      // THEN - the tick has the latest price and keeps the earlier change
+      assert_eq!(1, ticks.len());
+      let tick = ticks[0].as_ref().unwrap();
+      assert_eq!(150.5, tick.price);
+      assert_eq!(Some(1.5), tick.change);
+      assert_eq!(Some(1.01f32 as f64), tick.change_percent);
+   });
+}
+
+#[test]
+fn conflate_stream_window() {
+   //! Ensure that the stream emits once per window
+
+   block_on(async {
+      // GIVEN - updates that arrive in two bursts
+      let (tx, rx) = mpsc::unbounded_channel::<Result<Quote>>();
+      let conflated = Conflator::new(Duration::from_millis(100)).stream(rx);
+      tokio::spawn(async move {
+         for price in &[1.0, 2.0, 3.0] { tx.send(Ok(quote("AAPL", 1, *price))).unwrap(); }
+         tokio::time::delay_for(Duration::from_millis(250)).await;
+         for price in &[4.0, 5.0] { tx.send(Ok(quote("AAPL", 2, *price))).unwrap(); }
+      });
+
+      // WHEN - we conflate them
+      let quotes: Vec<_> = conflated.collect().await;
+
+      // THEN - we get the latest of each burst
+      let prices: Vec<f64> = quotes.iter().map(|quote| quote.as_ref().unwrap().price).collect();
+      assert_eq!(vec![3.0, 5.0], prices);
+   });
+}
+
+#[test]
+fn conflate_stream_outside_runtime() {
+   //! Ensure that a conflated stream can be built before a runtime is running
+
+   // GIVEN - a conflated stream built outside of any runtime
+   let quotes: Vec<Result<Quote>> = vec![Ok(quote("AAPL", 1, 10.0)), Ok(quote("AAPL", 2, 11.0))];
+   let conflated = Conflator::new(Duration::from_millis(100)).stream(futures::stream::iter(quotes));
+
+   // WHEN - it's polled within a runtime
+   let quotes: Vec<_> = block_on(conflated.collect());
+
+   // THEN - it conflates as usual
+   assert_eq!(1, quotes.len());
+   assert_eq!(11.0, quotes[0].as_ref().unwrap().price);
+}
+
+#[test]
+fn conflate_errors() {
+   //! Ensure that errors are passed along right away
+
+   block_on(async {
+      // GIVEN - a recording with bad lines between quotes for two symbols
+      let quotes = Replay::new("tests/replay_data/corrupt.rec").speed(ReplaySpeed::AsFastAsPossible).stream().await.unwrap();
+
+      // WHEN - we conflate them
+      let quotes: Vec<_> = Conflator::new(Duration::from_secs(10)).stream(quotes).collect().await;
+
+      // THEN - the errors come first and then the quotes
+      assert_eq!(4, quotes.len());
+      assert!(quotes[0].is_err());
+      assert!(quotes[1].is_err());
+      assert_eq!("AAPL", quotes[2].as_ref().unwrap().symbol);
+      assert_eq!("MSFT", quotes[3].as_ref().unwrap().symbol);
+   });
+}
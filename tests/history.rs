@@ -1,15 +1,18 @@
 use chrono::{Duration, Utc};
-use mockito::{mock, Mock};
-use std::env;
+use mockito::{mock, Matcher, Mock};
 use std::fs::File;
 use std::io::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use tokio_test::block_on;
-use yahoo_finance::{history, Interval};
+use yahoo_finance::{Client, Interval};
+use yahoo_finance::history::{Dividend, Events, Split};
 
-fn base_mock(test_name: &str, symbol: &str, query: &str) -> std::io::Result<Mock> {
+fn client() -> Client {
    // Tell the actual code to use a test URL rather than the live one
-   env::set_var("TEST_URL", mockito::server_url());
+   Client::builder().chart_url(&mockito::server_url()).build().unwrap()
+}
 
+fn base_mock(test_name: &str, symbol: &str, query: &str) -> std::io::Result<Mock> {
    // Load the simulated Yahoo data we want to test against
    let mut file = File::open(format!("tests/history_data/{}.json", test_name))?;
    let mut contents = String::new();
@@ -33,8 +36,8 @@ fn retrieve_valid() {
    let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we load the data
-   let result = block_on(history::retrieve(symbol)).unwrap();
-   assert!(result.len() > 0)
+   let result = block_on(client().history().retrieve(symbol)).unwrap();
+   assert!(!result.is_empty())
 }
 
 #[test]
@@ -47,7 +50,7 @@ fn retrieve_invalid_symbol() {
    let _m = base_mock("not_found", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we load the data
-   block_on(history::retrieve(symbol)).unwrap();
+   block_on(client().history().retrieve(symbol)).unwrap();
 
    // THEN - we get an error
 }
@@ -62,7 +65,7 @@ fn retrieve_interval_invalid() {
    let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we get a date range where the start date is after the end date
-   block_on(history::retrieve_interval(symbol, Interval::_1m)).unwrap();
+   block_on(client().history().retrieve_interval(symbol, Interval::_1m)).unwrap();
 
    // THEN - we get an error
 }
@@ -77,7 +80,7 @@ fn retrieve_range_invalid1() {
    let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we get a date range where the start date is after the end date
-   block_on(history::retrieve_range(symbol, Utc::now() - Duration::days(10), Some(Utc::now() - Duration::days(15)))).unwrap();
+   block_on(client().history().retrieve_range(symbol, Utc::now() - Duration::days(10), Some(Utc::now() - Duration::days(15)))).unwrap();
 
    // THEN - we get an error
 }
@@ -92,7 +95,7 @@ fn retrieve_range_invalid2() {
    let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we get a date range where the start date is after the end date
-   block_on(history::retrieve_range(symbol, Utc::now() + Duration::days(10), None)).unwrap();
+   block_on(client().history().retrieve_range(symbol, Utc::now() + Duration::days(10), None)).unwrap();
 
    // THEN - we get an error
 }
@@ -107,8 +110,8 @@ fn retrieve_no_quote_data() {
    let _m = base_mock("no_quote_data", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we get data where the there is basically no data
-   let result = block_on(history::retrieve(symbol)).unwrap();
-   assert!(result.len() == 0)
+   let result = block_on(client().history().retrieve(symbol)).unwrap();
+   assert!(result.is_empty())
 }
 
 #[test]
@@ -122,7 +125,279 @@ fn retrieve_no_timestamp_data() {
    let _m = base_mock("no_timestamp_data", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we get data where the there are no quotes
-   block_on(history::retrieve(symbol)).unwrap();
+   block_on(client().history().retrieve(symbol)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn retrieve_intraday_valid() {
+   //! Ensure that we can load intraday bars
+
+   // GIVEN - a valid response and stock symbol
+   let symbol = "AAPL";
+   let query = format!("range={r}&interval={i}", r=Interval::_1d, i=Interval::_5m);
+   let _m = base_mock("aapl", symbol, query.as_str()).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(client().history().retrieve_intraday(symbol, Interval::_5m, Interval::_1d)).unwrap();
+
+   // THEN - we get bars back
+   assert!(!result.is_empty())
+}
+
+#[test]
+#[should_panic(expected = "NotIntraday")]
+fn retrieve_intraday_not_intraday() {
+   //! Ensure that we gracefully fail when we ask for intraday data with a daily granularity
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+
+   // WHEN - we ask for daily bars through the intraday call
+   block_on(client().history().retrieve_intraday(symbol, Interval::_1d, Interval::_5d)).unwrap();
 
    // THEN - we get an error
 }
+
+#[test]
+#[should_panic(expected = "IntradayTooLong")]
+fn retrieve_intraday_too_long() {
+   //! Ensure that we gracefully fail when we ask for more 1 minute data than Yahoo! allows
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+
+   // WHEN - we ask for a month of 1 minute bars
+   block_on(client().history().retrieve_intraday(symbol, Interval::_1m, Interval::_1mo)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+#[should_panic(expected = "IntradayTooLong")]
+fn retrieve_intraday_90m_too_long() {
+   //! Ensure that 90 minute bars are limited like the other intraday granularities
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+
+   // WHEN - we ask for 3 months of 90 minute bars
+   block_on(client().history().retrieve_intraday(symbol, Interval::_90m, Interval::_3mo)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+#[should_panic(expected = "IntradayTooOld")]
+fn retrieve_intraday_range_too_old() {
+   //! Ensure that we gracefully fail when we ask for intraday data that Yahoo! no longer keeps
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+
+   // WHEN - we ask for 5 minute bars from 90 days ago
+   let start = Utc::now() - Duration::days(90);
+   block_on(client().history().retrieve_intraday_range(symbol, Interval::_5m, start, Some(start + Duration::days(1)))).unwrap();
+
+   // THEN - we get an error
+}
+
+fn range_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/history_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data for any date range on the test URL
+   Ok(mock("GET", Matcher::Regex(format!(r"^/{}\?period1=\d+&period2=\d+&interval=", symbol)))
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn retrieve_intraday_chunked_valid() {
+   //! Ensure that a long intraday range is split up and stitched back together
+
+   // GIVEN - a valid response for every window
+   let symbol = "AAPL";
+   let _m = range_mock("aapl", symbol).unwrap().expect(3).create();
+
+   // WHEN - we load 20 days of 1 minute bars - which takes 3 calls
+   let start = Utc::now() - Duration::days(20);
+   let result = block_on(client().history().retrieve_intraday_chunked(symbol, Interval::_1m, start, None, 2)).unwrap();
+
+   // THEN - the duplicate bars are removed and they are in order
+   _m.assert();
+   assert!(result.failures.is_empty());
+   assert_eq!(5, result.bars.len());
+   assert!(result.bars.windows(2).all(|pair| pair[0].timestamp < pair[1].timestamp));
+}
+
+#[test]
+fn retrieve_intraday_chunked_failures() {
+   //! Ensure that failing windows are reported rather than failing everything
+
+   // GIVEN - an invalid symbol
+   let symbol = "FUBAR";
+   let _m = range_mock("not_found", symbol).unwrap().create();
+
+   // WHEN - we load 20 days of 1 minute bars
+   let start = Utc::now() - Duration::days(20);
+   let result = block_on(client().history().retrieve_intraday_chunked(symbol, Interval::_1m, start, None, 2)).unwrap();
+
+   // THEN - every window is reported as failed
+   assert!(result.bars.is_empty());
+   assert_eq!(3, result.failures.len());
+   assert!(result.failures.windows(2).all(|pair| pair[0].end == pair[1].start));
+}
+
+#[test]
+fn retrieve_adjusted_valid() {
+   //! Ensure that adjusted closes are surfaced and can adjust the entire bar
+
+   // GIVEN - a response where the history has been adjusted for a 2:1 split
+   let symbol = "AAPL";
+   let _m = base_mock("split", symbol, build_interval(Interval::_1y).as_str()).unwrap().create();
+
+   // WHEN - we load the adjusted data
+   let result = block_on(client().history().retrieve_adjusted(symbol, Interval::_1y)).unwrap();
+
+   // THEN - each bar has its adjusted close and adjusting halves the prices
+   assert_eq!(5, result.len());
+   for bar in &result {
+      let adjusted = bar.adjusted();
+      assert_eq!(Some(bar.bar.close / 2.0), bar.adjclose);
+      assert_eq!(bar.bar.close / 2.0, adjusted.close);
+      assert_eq!(bar.bar.open / 2.0, adjusted.open);
+      assert_eq!(bar.bar.high / 2.0, adjusted.high);
+      assert_eq!(bar.bar.low / 2.0, adjusted.low);
+      assert_eq!(bar.bar.volume, adjusted.volume);
+   }
+}
+
+#[test]
+fn retrieve_events_valid() {
+   //! Ensure that we can load dividends & splits
+
+   // GIVEN - a valid response with events
+   let symbol = "AAPL";
+   let _m = range_mock("events", symbol).unwrap().create();
+
+   // WHEN - we load the events
+   let result = block_on(client().history().retrieve_events(symbol, Utc::now() - Duration::days(365), None)).unwrap();
+
+   // THEN - we get them back in order
+   assert_eq!(vec![
+      Dividend { timestamp: 1581085800000, amount: 0.77 },
+      Dividend { timestamp: 1588901400000, amount: 0.82 }
+   ], result.dividends);
+   assert_eq!(vec![Split { timestamp: 1598880600000, numerator: 4.0, denominator: 1.0 }], result.splits);
+}
+
+#[test]
+fn retrieve_events_bad_bars() {
+   //! Ensure that bars we can't read don't stop us from loading the events
+
+   // GIVEN - a response with events but bars that aren't valid
+   let symbol = "MSFT";
+   let _m = range_mock("events_bad_bars", symbol).unwrap().create();
+
+   // WHEN - we load the events
+   let result = block_on(client().history().retrieve_events(symbol, Utc::now() - Duration::days(365), None)).unwrap();
+
+   // THEN - we still get them
+   assert_eq!(2, result.dividends.len());
+   assert_eq!(1, result.splits.len());
+}
+
+#[test]
+fn retrieve_range_with_events_valid() {
+   //! Ensure that we can load bars and events together - and that no events is not an error
+
+   // GIVEN - a valid response without any events
+   let symbol = "AAPL";
+   let _m = range_mock("aapl", symbol).unwrap().create();
+
+   // WHEN - we load the bars and events
+   let (bars, events) = block_on(client().history().retrieve_range_with_events(symbol, Utc::now() - Duration::days(365), None)).unwrap();
+
+   // THEN - we get bars and no events
+   assert_eq!(5, bars.len());
+   assert_eq!(Events::default(), events);
+}
+
+#[test]
+fn retrieve_with_meta_valid() {
+   //! Ensure that the chart metadata comes back along with the bars
+
+   // GIVEN - a valid response and stock symbol
+   let symbol = "AAPL";
+   let _m = base_mock("aapl", symbol, build_interval(Interval::_5d).as_str()).unwrap().create();
+
+   // WHEN - we load the data
+   let (meta, bars) = block_on(client().history().retrieve_with_meta(symbol, Interval::_5d)).unwrap();
+
+   // THEN - we get the metadata we expect
+   assert_eq!(5, bars.len());
+   assert_eq!("AAPL", meta.symbol);
+   assert_eq!(Some("USD".to_string()), meta.currency);
+   assert_eq!(Some("NMS".to_string()), meta.exchange_name);
+   assert_eq!(Some("EQUITY".to_string()), meta.instrument_type);
+   assert_eq!(Some("America/New_York".to_string()), meta.exchange_timezone_name);
+   assert_eq!(Some(-14400), meta.gmt_offset);
+   assert_eq!(Some(2), meta.price_hint);
+   assert_eq!(11, meta.valid_ranges.len());
+
+   let periods = meta.current_trading_period.unwrap();
+   assert_eq!(1588339800, periods.regular.start.timestamp());
+   assert_eq!(1588363200, periods.regular.end.timestamp());
+   assert_eq!("EDT", periods.pre.timezone);
+}
+
+#[test]
+fn retrieve_batch_valid() {
+   //! Ensure that many symbols can be loaded at once - and that one bad symbol doesn't fail the rest
+
+   // GIVEN - a good and a bad symbol
+   let _good = range_mock("aapl", "AAPL").unwrap().create();
+   let _bad = range_mock("not_found", "FUBAR").unwrap().create();
+   let finished = AtomicUsize::new(0);
+   let progress = |_: &str, done: usize, total: usize| {
+      assert_eq!(2, total);
+      finished.store(done, Ordering::SeqCst);
+   };
+
+   // WHEN - we load them together
+   let start = Utc::now() - Duration::days(30);
+   let result = block_on(client().history().retrieve_batch(&["AAPL", "FUBAR"], start, None, 2, Some(&progress)));
+
+   // THEN - each symbol has its own result and we saw the progress
+   assert_eq!(2, result.len());
+   assert_eq!(5, result["AAPL"].as_ref().unwrap().len());
+   assert!(format!("{:?}", result["FUBAR"].as_ref().unwrap_err()).contains("Not Found"));
+   assert_eq!(2, finished.load(Ordering::SeqCst));
+}
+
+#[test]
+fn retrieve_batch_duplicates() {
+   //! Ensure that a symbol given more than once is only loaded and counted once
+
+   // GIVEN - a good symbol that's only expected to be asked for once
+   let _m = range_mock("aapl", "AAPL").unwrap().expect(1).create();
+   let finished = AtomicUsize::new(0);
+   let progress = |_: &str, done: usize, total: usize| {
+      assert_eq!(1, total);
+      finished.store(done, Ordering::SeqCst);
+   };
+
+   // WHEN - we load it twice in the same batch
+   let start = Utc::now() - Duration::days(30);
+   let result = block_on(client().history().retrieve_batch(&["AAPL", "AAPL"], start, None, 2, Some(&progress)));
+
+   // THEN - there's one result and the progress matches it
+   _m.assert();
+   assert_eq!(1, result.len());
+   assert_eq!(1, finished.load(Ordering::SeqCst));
+}
@@ -0,0 +1,127 @@
+use futures::StreamExt;
+use std::time::Duration;
+use tokio_test::block_on;
+use yahoo_finance::test_support::{FrameBuilder, MockStreamServer, Step};
+use yahoo_finance::{Client, LagPolicy, QuoteHub};
+
+async fn hub(server: &MockStreamServer, capacity: usize, lag: LagPolicy) -> QuoteHub {
+   // Tell the actual code to use the mock server rather than the live one
+   let streamer = Client::builder().streaming_url(&server.url()).build().unwrap().streamer(Vec::new());
+   QuoteHub::start(streamer, capacity, lag).await.unwrap()
+}
+
+fn frame(symbol: &str, price: f32) -> Step {
+   Step::Send(FrameBuilder::new(symbol).price(price).build())
+}
+
+#[test]
+fn hub_fan_out() {
+   //! Ensure that consumers share the connection and only get their symbols
+
+   block_on(async {
+      // GIVEN - a server that sends a quote for each symbol
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive, Step::Receive, frame("AAPL", 150.0), frame("MSFT", 200.0), Step::Close
+      ]]).await;
+      let hub = hub(&server, 16, LagPolicy::DropOldest).await;
+
+      // WHEN - two consumers want overlapping symbols
+      let apple = hub.subscribe(&["AAPL"]);
+      let tech = hub.subscribe(&["AAPL", "MSFT"]);
+      let apple: Vec<_> = apple.collect().await;
+      let tech: Vec<_> = tech.collect().await;
+
+      // THEN - each symbol is only subscribed to once
+      assert_eq!(vec![
+         r#"{"subscribe":["AAPL"]}"#.to_string(),
+         r#"{"subscribe":["MSFT"]}"#.to_string()
+      ], server.received());
+
+      // AND - each consumer gets the ticks for its symbols
+      assert_eq!(1, apple.len());
+      assert_eq!("AAPL", apple[0].as_ref().unwrap().symbol);
+      assert_eq!(2, tech.len());
+      assert_eq!("MSFT", tech[1].as_ref().unwrap().symbol);
+   });
+}
+
+#[test]
+fn hub_release() {
+   //! Ensure that a symbol is unsubscribed once the last consumer is gone
+
+   block_on(async {
+      // GIVEN - two consumers of the same symbol and one of another
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive, Step::Receive, Step::Receive, Step::Close
+      ]]).await;
+      let hub = hub(&server, 16, LagPolicy::DropOldest).await;
+      let first = hub.subscribe(&["AAPL"]);
+      let second = hub.subscribe(&["AAPL"]);
+      let other = hub.subscribe(&["MSFT"]);
+
+      // WHEN - the consumers of the shared symbol go away
+      drop(first);
+      assert_eq!(vec!["AAPL".to_string(), "MSFT".to_string()], hub.symbols());
+      drop(second);
+
+      // THEN - the symbol is unsubscribed once both are gone
+      assert_eq!(vec!["MSFT".to_string()], hub.symbols());
+      other.collect::<Vec<_>>().await;
+      assert_eq!(r#"{"unsubscribe":["AAPL"]}"#, server.received()[2]);
+   });
+}
+
+#[test]
+fn hub_drop_oldest() {
+   //! Ensure that a slow consumer loses its oldest ticks
+
+   block_on(async {
+      // GIVEN - a server that sends more ticks than a consumer can hold
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive,
+         frame("AAPL", 1.0), frame("AAPL", 2.0), frame("AAPL", 3.0), frame("AAPL", 4.0), frame("AAPL", 5.0),
+         Step::Close
+      ]]).await;
+      let hub = hub(&server, 2, LagPolicy::DropOldest).await;
+      let slow = hub.subscribe(&["AAPL"]);
+
+      // WHEN - the consumer doesn't keep up
+      tokio::time::delay_for(Duration::from_millis(300)).await;
+      let ticks: Vec<_> = slow.collect().await;
+
+      // THEN - it only gets the newest ticks
+      let prices: Vec<f64> = ticks.iter().map(|tick| tick.as_ref().unwrap().price).collect();
+      assert_eq!(vec![4.0, 5.0], prices);
+   });
+}
+
+#[test]
+fn hub_disconnect() {
+   //! Ensure that a slow consumer can be disconnected
+
+   block_on(async {
+      // GIVEN - a server that sends more ticks than a consumer can hold
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive, Step::Receive,
+         frame("AAPL", 1.0), frame("AAPL", 2.0), frame("AAPL", 3.0), frame("AAPL", 4.0),
+         Step::Receive, Step::Close
+      ]]).await;
+      let hub = hub(&server, 2, LagPolicy::Disconnect).await;
+      let slow = hub.subscribe(&["AAPL"]);
+      let other = hub.subscribe(&["MSFT"]);
+
+      // WHEN - the consumer doesn't keep up
+      tokio::time::delay_for(Duration::from_millis(300)).await;
+      let ticks: Vec<_> = slow.collect().await;
+
+      // THEN - it gets what it had room for and then an error
+      assert_eq!(3, ticks.len());
+      assert_eq!(1.0, ticks[0].as_ref().unwrap().price);
+      assert_eq!(2.0, ticks[1].as_ref().unwrap().price);
+      assert!(format!("{:?}", ticks[2]).contains("HubLagged"));
+
+      // AND - its symbols were released
+      other.collect::<Vec<_>>().await;
+      assert_eq!(r#"{"unsubscribe":["AAPL"]}"#, server.received()[2]);
+   });
+}
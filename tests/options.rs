@@ -0,0 +1,122 @@
+use chrono::{TimeZone, Utc};
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::Client;
+use yahoo_finance::options::OptionKind;
+
+fn client() -> Client {
+   // Tell the actual code to use a test URL rather than the live one
+   Client::builder().options_url(&mockito::server_url()).build().unwrap()
+}
+
+fn base_mock(test_name: &str, path: &str) -> std::io::Result<Mock> {
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/options_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", path)
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn expirations_valid() {
+   //! Ensure that we can list the expiration dates for a symbol
+
+   // GIVEN - a valid response and stock symbol
+   let _m = base_mock("aapl", "/AAPL").unwrap().create();
+
+   // WHEN - we load the expiration dates
+   let result = block_on(client().options().expirations("AAPL")).unwrap();
+
+   // THEN - we get all of them
+   assert_eq!(vec![
+      Utc.timestamp_opt(1589500800, 0).unwrap(),
+      Utc.timestamp_opt(1590105600, 0).unwrap(),
+      Utc.timestamp_opt(1590710400, 0).unwrap()
+   ], result);
+}
+
+#[test]
+fn expirations_no_options() {
+   //! Ensure that a symbol without options has no expiration dates
+
+   // GIVEN - an empty response
+   let _m = base_mock("empty", "/FUBAR").unwrap().create();
+
+   // WHEN - we load the expiration dates
+   let result = block_on(client().options().expirations("FUBAR")).unwrap();
+
+   // THEN - there are none
+   assert!(result.is_empty());
+}
+
+#[test]
+fn chain_valid() {
+   //! Ensure that we can load the calls & puts for an expiration
+
+   // GIVEN - a valid response for an expiration
+   let _m = base_mock("aapl", "/AAPL?date=1589500800").unwrap().create();
+   let expiration = Utc.timestamp_opt(1589500800, 0).unwrap();
+
+   // WHEN - we load the chain
+   let result = block_on(client().options().chain("AAPL", expiration)).unwrap();
+
+   // THEN - we get the contracts ordered by strike
+   assert_eq!("AAPL", result.underlying);
+   assert_eq!(expiration, result.expiration);
+   assert_eq!(2, result.calls.len());
+   assert_eq!(2, result.puts.len());
+
+   let call = &result.calls[0];
+   assert_eq!("AAPL200515C00280000", call.contract_symbol);
+   assert_eq!(OptionKind::Call, call.kind);
+   assert_eq!(280.0, call.strike);
+   assert_eq!(expiration, call.expiration);
+   assert_eq!(Some(14.2), call.bid);
+   assert_eq!(Some(14.5), call.ask);
+   assert_eq!(Some(800), call.volume);
+   assert_eq!(Some(3100), call.open_interest);
+   assert_eq!(Some(0.45), call.implied_volatility);
+   assert!(call.in_the_money);
+
+   let put = &result.puts[1];
+   assert_eq!(OptionKind::Put, put.kind);
+   assert_eq!(300.0, put.strike);
+   assert!(put.in_the_money);
+}
+
+#[test]
+fn chain_any_time_of_day() {
+   //! Ensure that an expiration that isn't exactly one of Yahoo!'s still finds the chain
+
+   // GIVEN - a valid response for an expiration
+   let _m = base_mock("aapl", "/AAPL?date=1589500800").unwrap().create();
+
+   // WHEN - we load the chain for the afternoon of the expiration date
+   let result = block_on(client().options().chain("AAPL", Utc.timestamp_opt(1589500800 + 14 * 3600, 0).unwrap())).unwrap();
+
+   // THEN - we get the contracts for that date
+   assert_eq!(Utc.timestamp_opt(1589500800, 0).unwrap(), result.expiration);
+   assert_eq!(2, result.calls.len());
+   assert_eq!(2, result.puts.len());
+}
+
+#[test]
+#[should_panic(expected = "BadData")]
+fn chain_bad_data() {
+   //! Ensures that we gracefully fail when Yahoo! sends back bad JSON
+
+   // GIVEN - a response that isn't an option chain
+   let _m = base_mock("../snapshot_data/empty", "/NULL?date=1589500800").unwrap().create();
+
+   // WHEN - we load the chain
+   block_on(client().options().chain("NULL", Utc.timestamp_opt(1589500800, 0).unwrap())).unwrap();
+
+   // THEN - we get an error
+}
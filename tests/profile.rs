@@ -1,14 +1,15 @@
 use mockito::{mock, Mock};
-use std::env;
 use std::fs::File;
 use std::io::prelude::*;
 use tokio_test::block_on;
-use yahoo_finance::{Profile};
+use yahoo_finance::{Client, Profile};
 
-fn base_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
+fn client() -> Client {
    // Tell the actual code to use a test URL rather than the live one
-   env::set_var("TEST_URL", mockito::server_url());
+   Client::builder().profile_url(&mockito::server_url()).build().unwrap()
+}
 
+fn base_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
    // Load the simulated Yahoo data we want to test against
    let mut file = File::open(format!("tests/profile_data/{}.html", test_name))?;
    let mut contents = String::new();
@@ -30,7 +31,7 @@ fn load_company() {
    let _m = base_mock("aapl", symbol).unwrap().create();
 
    // WHEN - we load the data
-   let result = block_on(Profile::load(symbol)).unwrap();
+   let result = block_on(client().profile(symbol)).unwrap();
 
    // THEN - we get the results we expect
    match result {
@@ -53,7 +54,7 @@ fn load_fund() {
    let _m = base_mock("qqq", symbol).unwrap().create();
 
    // WHEN - we load the data
-   let result = block_on(Profile::load(symbol)).unwrap();
+   let result = block_on(client().profile(symbol)).unwrap();
 
    // THEN - we get the results we expect
    match result {
@@ -77,7 +78,7 @@ fn load_bad_data() {
    let _m = base_mock("invalid_json", symbol).unwrap().create();
 
    // WHEN - we load the data
-   block_on(Profile::load(symbol)).expect("failure");
+   block_on(client().profile(symbol)).expect("failure");
 
    // THEN - we get an error
 }
@@ -94,7 +95,7 @@ fn load_bad_response() {
       .create();
 
    // WHEN - we load the data
-   block_on(Profile::load(symbol)).expect("failure");
+   block_on(client().profile(symbol)).expect("failure");
 
    // THEN - we get an error
 }
@@ -109,7 +110,7 @@ fn load_missing_data() {
    let _m = base_mock("missing_data", symbol).unwrap().create();
 
    // WHEN - we load the data
-   block_on(Profile::load(symbol)).expect("failure");
+   block_on(client().profile(symbol)).expect("failure");
 
    // THEN - we get an error
 }
@@ -0,0 +1,164 @@
+use futures::StreamExt;
+use std::time::{Duration, Instant};
+use tokio_test::block_on;
+use yahoo_finance::{options::OptionKind, QuoteType, Replay, ReplaySpeed, TypedTick};
+
+fn replay(test_name: &str) -> Replay {
+   Replay::new(format!("tests/replay_data/{}.rec", test_name)).speed(ReplaySpeed::AsFastAsPossible)
+}
+
+#[test]
+fn replay_quotes() {
+   //! Ensure that a recording plays back as quotes
+
+   // GIVEN - a recording of a few frames
+
+   // WHEN - we replay it
+   let quotes: Vec<_> = block_on(async { replay("aapl").stream().await.unwrap().collect().await });
+
+   // THEN - we get every quote in order
+   assert_eq!(3, quotes.len());
+   let first = quotes[0].as_ref().unwrap();
+   assert_eq!("AAPL", first.symbol);
+   assert_eq!(1_599_999_999_995, first.timestamp);
+   assert_eq!(150.25, first.price);
+   assert_eq!(1000, first.volume);
+   assert_eq!("MSFT", quotes[2].as_ref().unwrap().symbol);
+}
+
+#[test]
+fn replay_ticks() {
+   //! Ensure that a recording plays back with everything Yahoo! sent
+
+   // GIVEN - a recording with a full level 1 frame
+
+   // WHEN - we replay it as ticks
+   let ticks: Vec<_> = block_on(async { replay("aapl").stream_ticks().await.unwrap().collect().await });
+
+   // THEN - the full frame has everything
+   let tick = ticks[0].as_ref().unwrap();
+   assert_eq!(Some(QuoteType::Equity), tick.quote_type);
+   assert_eq!(Some(150.2), tick.bid.map(|bid| (bid * 100.0).round() / 100.0));
+   assert_eq!(Some(3), tick.bid_size);
+   assert_eq!(Some(5), tick.ask_size);
+   assert_eq!(Some(100), tick.last_size);
+   assert_eq!(Some("USD".to_string()), tick.currency);
+   assert_eq!(Some("Apple Inc.".to_string()), tick.short_name);
+   assert_eq!(Some(2.5e12), tick.market_cap);
+
+   // AND - values that weren't sent are missing
+   let tick = ticks[1].as_ref().unwrap();
+   assert_eq!(None, tick.bid);
+   assert_eq!(None, tick.currency);
+   assert_eq!(Some(1200), tick.day_volume);
+}
+
+#[test]
+fn replay_typed() {
+   //! Ensure that option and crypto frames have their own details
+
+   // GIVEN - a recording with options, a cryptocurrency and an index
+
+   // WHEN - we replay it as typed ticks
+   let ticks: Vec<_> = block_on(async { replay("mixed").stream_typed().await.unwrap().collect().await });
+
+   // THEN - the options have their details
+   assert_eq!(4, ticks.len());
+   match ticks[0].as_ref().unwrap() {
+      TypedTick::Option { tick, option } => {
+         assert_eq!("AAPL200918C00150000", tick.symbol);
+         assert_eq!(Some(OptionKind::Call), option.kind);
+         assert_eq!(Some(150.0), option.strike);
+         assert_eq!(Some("AAPL".to_string()), option.underlying);
+         assert_eq!(Some(1_600_387_200), option.expiration.map(|expiration| expiration.timestamp()));
+         assert_eq!(Some(1234), option.open_interest);
+         assert!(!option.mini);
+      },
+      other => panic!("expected an option - got {:?}", other)
+   }
+   match ticks[1].as_ref().unwrap() {
+      TypedTick::Option { option, .. } => {
+         assert_eq!(Some(OptionKind::Put), option.kind);
+         assert!(option.mini);
+      },
+      other => panic!("expected an option - got {:?}", other)
+   }
+
+   // AND - the cryptocurrency has its details
+   match ticks[2].as_ref().unwrap() {
+      TypedTick::Crypto { tick, crypto } => {
+         assert_eq!("BTC-USD", tick.symbol);
+         assert_eq!(Some("BTC".to_string()), crypto.from_currency);
+         assert_eq!(Some(30_000_000_000), crypto.volume_24h);
+         assert_eq!(Some(18_500_000.0), crypto.circulating_supply);
+      },
+      other => panic!("expected a cryptocurrency - got {:?}", other)
+   }
+
+   // AND - everything else is just a tick
+   match ticks[3].as_ref().unwrap() {
+      TypedTick::Other(tick) => assert_eq!("^DJI", tick.symbol),
+      other => panic!("expected an index - got {:?}", other)
+   }
+}
+
+#[test]
+fn replay_heartbeats() {
+   //! Ensure that heartbeats are skipped - just like when streaming
+
+   // GIVEN - a recording with a heartbeat between two quotes
+
+   // WHEN - we replay it
+   let quotes: Vec<_> = block_on(async { replay("heartbeat").stream().await.unwrap().collect().await });
+
+   // THEN - we only get the quotes
+   assert_eq!(2, quotes.len());
+   assert!(quotes.iter().all(|quote| quote.as_ref().unwrap().symbol == "AAPL"));
+}
+
+#[test]
+fn replay_corrupt() {
+   //! Ensure that bad lines are errors without ending the replay
+
+   // GIVEN - a recording with a bad line and a bad frame
+
+   // WHEN - we replay it
+   let quotes: Vec<_> = block_on(async { replay("corrupt").stream().await.unwrap().collect().await });
+
+   // THEN - the bad lines are errors between the good quotes
+   assert_eq!(4, quotes.len());
+   assert!(quotes[0].is_ok());
+   assert!(quotes[1].is_err());
+   assert!(quotes[2].is_err());
+   assert_eq!("MSFT", quotes[3].as_ref().unwrap().symbol);
+}
+
+#[test]
+fn replay_original_speed() {
+   //! Ensure that a recording plays back with its original gaps
+
+   // GIVEN - a recording spread over 200ms
+   let replay = replay("aapl").speed(ReplaySpeed::Original);
+
+   // WHEN - we replay it
+   let start = Instant::now();
+   let quotes: Vec<_> = block_on(async { replay.stream().await.unwrap().collect().await });
+
+   // THEN - it takes as long as the recording did
+   assert_eq!(3, quotes.len());
+   assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[test]
+#[should_panic(expected = "Recording")]
+fn replay_missing() {
+   //! Ensure that a missing recording is an error
+
+   // GIVEN - a recording that doesn't exist
+
+   // WHEN - we replay it
+   let result = block_on(async { replay("missing").stream().await.map(|_| ()) });
+
+   // THEN - we get an error
+   result.unwrap();
+}
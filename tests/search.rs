@@ -0,0 +1,84 @@
+use mockito::{mock, Matcher, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::{Client, QuoteType};
+
+fn client() -> Client {
+   // Tell the actual code to use a test URL rather than the live one
+   Client::builder().search_url(&mockito::server_url()).build().unwrap()
+}
+
+fn base_mock(test_name: &str, query: &str) -> std::io::Result<Mock> {
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/search_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", "/")
+      .match_query(Matcher::UrlEncoded("q".into(), query.into()))
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn search_valid() {
+   //! Ensure that we can search for symbols
+
+   // GIVEN - a valid response for a search
+   let _m = base_mock("apple", "apple").unwrap().create();
+
+   // WHEN - we search
+   let result = block_on(client().search().symbols("apple")).unwrap();
+
+   // THEN - we get all of the matches in order
+   assert_eq!(4, result.len());
+
+   let apple = &result[0];
+   assert_eq!("AAPL", apple.symbol);
+   assert_eq!(Some("Apple Inc.".to_string()), apple.short_name);
+   assert_eq!(Some("Apple Inc.".to_string()), apple.long_name);
+   assert_eq!(Some("NMS".to_string()), apple.exchange);
+   assert_eq!(Some("NASDAQ".to_string()), apple.exchange_display);
+   assert_eq!(QuoteType::Equity, apple.quote_type);
+
+   assert_eq!(QuoteType::Etf, result[2].quote_type);
+   assert_eq!(QuoteType::Option, result[3].quote_type);
+}
+
+#[test]
+fn search_of_type() {
+   //! Ensure that we can filter the matches by the kind of security
+
+   // GIVEN - a valid response for a search that looks beyond the usual matches
+   let _m = base_mock("apple", "apple inc").unwrap()
+      .match_query(Matcher::AllOf(vec![
+         Matcher::UrlEncoded("q".into(), "apple inc".into()),
+         Matcher::UrlEncoded("quotesCount".into(), "100".into())
+      ]))
+      .create();
+
+   // WHEN - we search for ETFs
+   let result = block_on(client().search().symbols_of_type("apple inc", QuoteType::Etf)).unwrap();
+
+   // THEN - we only get ETFs
+   _m.assert();
+   assert_eq!(1, result.len());
+   assert_eq!("AAPU", result[0].symbol);
+}
+
+#[test]
+#[should_panic(expected = "BadData")]
+fn search_bad_data() {
+   //! Ensures that we gracefully fail when Yahoo! sends back bad JSON
+
+   // GIVEN - a response that isn't JSON
+   let _m = mock("GET", "/").match_query(Matcher::Any).with_body("<html></html>").create();
+
+   // WHEN - we search
+   block_on(client().search().symbols("nothing")).unwrap();
+
+   // THEN - we get an error
+}
@@ -0,0 +1,108 @@
+use futures::StreamExt;
+use tokio_test::block_on;
+use yahoo_finance::test_support::{FrameBuilder, MockStreamServer, Step};
+use yahoo_finance::{Client, Session, SessionChange, SessionTracker, Streamer, TradingSession};
+
+fn streamer(server: &MockStreamServer) -> Streamer {
+   // Tell the actual code to use the mock server rather than the live one
+   Client::builder().streaming_url(&server.url()).build().unwrap().streamer(vec!["AAPL"])
+}
+
+fn frame(seconds: i64, session: Session) -> Step {
+   Step::Send(FrameBuilder::new("AAPL").price(150.0).time(seconds * 1_000).session(session).build())
+}
+
+async fn server() -> MockStreamServer {
+   MockStreamServer::start(vec![vec![
+      Step::Receive,
+      frame(1, Session::PreMarket),
+      frame(2, Session::Regular),
+      frame(3, Session::Regular),
+      frame(4, Session::AfterHours),
+      frame(5, Session::ExtendedHours),
+      Step::Close
+   ]]).await
+}
+
+#[test]
+fn sessions_extended_hours() {
+   //! Ensure that extended hours are kept apart from the other sessions
+
+   block_on(async {
+      // GIVEN - a server that sends an update for every session
+      let server = server().await;
+
+      // WHEN - we stream ticks and quotes
+      let ticks: Vec<_> = streamer(&server).stream_ticks().await.unwrap().collect().await;
+
+      // THEN - the tick knows it happened during extended hours
+      let tick = ticks[4].as_ref().unwrap();
+      assert_eq!(Session::ExtendedHours, tick.session);
+
+      // AND - the quote falls back to the closest trading session
+      assert!(matches!(tick.quote().session, TradingSession::Other));
+   });
+}
+
+#[test]
+fn sessions_filtered() {
+   //! Ensure that only the chosen sessions are streamed
+
+   block_on(async {
+      // GIVEN - a server that sends an update for every session
+      let server = server().await;
+
+      // WHEN - we only want the regular session
+      let streamer = streamer(&server).sessions(&[Session::Regular]);
+      let ticks: Vec<_> = streamer.stream_ticks().await.unwrap().collect().await;
+
+      // THEN - we only get the regular session's ticks
+      let times: Vec<i64> = ticks.iter().map(|tick| tick.as_ref().unwrap().timestamp).collect();
+      assert_eq!(vec![2_000, 3_000], times);
+   });
+}
+
+#[test]
+fn sessions_changes() {
+   //! Ensure that moving from one session to the next is reported
+
+   block_on(async {
+      // GIVEN - a server that sends an update for every session
+      let server = server().await;
+
+      // WHEN - we track the sessions
+      let ticks = streamer(&server).stream_ticks().await.unwrap();
+      let changes: Vec<_> = SessionTracker::new().stream(ticks).collect().await;
+
+      // THEN - we get the first session and every change after it
+      let changes: Vec<SessionChange> = changes.into_iter().map(|change| change.unwrap()).collect();
+      assert_eq!(4, changes.len());
+      assert_eq!(SessionChange { symbol: "AAPL".to_string(), timestamp: 1_000, from: None, to: Session::PreMarket }, changes[0]);
+      assert_eq!((Some(Session::PreMarket), Session::Regular, 2_000), (changes[1].from, changes[1].to, changes[1].timestamp));
+      assert_eq!((Some(Session::Regular), Session::AfterHours), (changes[2].from, changes[2].to));
+      assert_eq!((Some(Session::AfterHours), Session::ExtendedHours), (changes[3].from, changes[3].to));
+   });
+}
+
+#[test]
+fn sessions_late_tick() {
+   //! Ensure that a late tick doesn't move a symbol back to an earlier session
+
+   block_on(async {
+      // GIVEN - a symbol that has moved to after hours
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive, frame(10, Session::Regular), frame(20, Session::AfterHours), frame(15, Session::Regular), Step::Close
+      ]]).await;
+      let mut tracker = SessionTracker::new();
+      let ticks: Vec<_> = streamer(&server).stream_ticks().await.unwrap().collect().await;
+
+      // WHEN - a regular session tick from before the change arrives
+      assert!(tracker.push(ticks[0].as_ref().unwrap()).is_some());
+      assert!(tracker.push(ticks[1].as_ref().unwrap()).is_some());
+      let late = tracker.push(ticks[2].as_ref().unwrap());
+
+      // THEN - the symbol stays in after hours
+      assert!(late.is_none());
+      assert_eq!(Some(Session::AfterHours), tracker.session("AAPL"));
+   });
+}
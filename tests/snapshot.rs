@@ -0,0 +1,102 @@
+use mockito::{mock, Matcher, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::Client;
+
+fn client() -> Client {
+   // Tell the actual code to use a test URL rather than the live one
+   Client::builder().quote_url(&mockito::server_url()).build().unwrap()
+}
+
+fn base_mock(test_name: &str, symbols: Matcher) -> std::io::Result<Mock> {
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/snapshot_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", "/")
+      .match_query(symbols)
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn load_valid() {
+   //! Ensure that we can load quotes for many symbols at once
+
+   // GIVEN - a valid response for two symbols
+   let _m = base_mock("aapl_msft", Matcher::UrlEncoded("symbols".into(), "AAPL,MSFT".into())).unwrap().create();
+
+   // WHEN - we load the quotes
+   let result = block_on(client().snapshots(&["AAPL", "MSFT"])).unwrap();
+
+   // THEN - we get the quotes we expect
+   assert_eq!(2, result.len());
+
+   let apple = &result[0];
+   assert_eq!("AAPL", apple.symbol);
+   assert_eq!(Some("Apple Inc.".to_string()), apple.short_name);
+   assert_eq!(Some("REGULAR".to_string()), apple.market_state);
+   assert_eq!(Some("USD".to_string()), apple.currency);
+   assert_eq!(Some(1588363201000), apple.timestamp);
+   assert_eq!(Some(289.07), apple.price);
+   assert_eq!(Some(289.1), apple.bid);
+   assert_eq!(Some(289.2), apple.ask);
+   assert_eq!(Some(285.85), apple.day_low);
+   assert_eq!(Some(299.0), apple.day_high);
+   assert_eq!(Some(170.27), apple.fifty_two_week_low);
+   assert_eq!(Some(327.85), apple.fifty_two_week_high);
+   assert_eq!(Some(60154175), apple.volume);
+   assert_eq!(Some(1252654661632), apple.market_cap);
+
+   let microsoft = &result[1];
+   assert_eq!("MSFT", microsoft.symbol);
+   assert_eq!(None, microsoft.bid);
+}
+
+#[test]
+fn load_unknown_symbol() {
+   //! Ensure that symbols Yahoo! doesn't know about are simply left out
+
+   // GIVEN - an empty response
+   let _m = base_mock("empty", Matcher::UrlEncoded("symbols".into(), "FUBAR".into())).unwrap().create();
+
+   // WHEN - we load the quotes
+   let result = block_on(client().snapshots(&["FUBAR"])).unwrap();
+
+   // THEN - there is nothing
+   assert!(result.is_empty());
+}
+
+#[test]
+fn load_many_symbols() {
+   //! Ensure that large numbers of symbols are split over multiple calls
+
+   // GIVEN - 250 symbols
+   let symbols: Vec<String> = (0..250).map(|i| format!("S{}", i)).collect();
+   let symbols: Vec<&str> = symbols.iter().map(String::as_str).collect();
+   let _m = base_mock("empty", Matcher::Regex("^symbols=S".into())).unwrap().expect(2).create();
+
+   // WHEN - we load the quotes
+   block_on(client().snapshots(&symbols)).unwrap();
+
+   // THEN - it took 2 calls
+   _m.assert();
+}
+
+#[test]
+#[should_panic(expected = "BadData")]
+fn load_bad_data() {
+   //! Ensures that we gracefully fail when Yahoo! sends back bad JSON
+
+   // GIVEN - a response that isn't a quote response
+   let _m = base_mock("../history_data/not_found", Matcher::Any).unwrap().create();
+
+   // WHEN - we load the quotes
+   block_on(client().snapshots(&["NULL"])).unwrap();
+
+   // THEN - we get an error
+}
@@ -0,0 +1,515 @@
+use futures::StreamExt;
+use std::time::Duration;
+use tokio_test::block_on;
+use yahoo_finance::test_support::{FrameBuilder, MockStreamServer, Step};
+use yahoo_finance::options::OptionKind;
+use yahoo_finance::{Client, ConnectionState, QuoteType, ReconnectPolicy, Recorder, Replay, ReplaySpeed, Session, Streamer, TypedTick};
+
+fn streamer(server: &MockStreamServer, symbols: Vec<&str>) -> Streamer {
+   // Tell the actual code to use the mock server rather than the live one
+   Client::builder().streaming_url(&server.url()).build().unwrap().streamer(symbols)
+}
+
+fn reconnect() -> ReconnectPolicy {
+   ReconnectPolicy { max_attempts: Some(2), initial_backoff: Duration::from_millis(10), jitter: false, ..ReconnectPolicy::default() }
+}
+
+fn frame(symbol: &str, price: f32) -> Step {
+   Step::Send(FrameBuilder::new(symbol).price(price).time(1_600_000_000_000).session(Session::Regular).day_volume(1000).build())
+}
+
+#[test]
+fn stream_quotes() {
+   //! Ensure that we subscribe and get the quotes that are sent
+
+   block_on(async {
+      // GIVEN - a server that sends two quotes
+      let server = MockStreamServer::start(vec![vec![Step::Receive, frame("AAPL", 150.0), frame("MSFT", 200.5), Step::Close]]).await;
+
+      // WHEN - we stream
+      let quotes: Vec<_> = streamer(&server, vec!["AAPL", "MSFT"]).stream().await.unwrap().collect().await;
+
+      // THEN - we subscribed to the symbols
+      assert_eq!(vec![r#"{"subscribe":["AAPL","MSFT"]}"#.to_string()], server.received());
+
+      // AND - we get the quotes until the server closes the connection
+      assert_eq!(2, quotes.len());
+      let aapl = quotes[0].as_ref().unwrap();
+      assert_eq!("AAPL", aapl.symbol);
+      assert_eq!(150.0, aapl.price);
+      assert_eq!(1_600_000_000_000, aapl.timestamp);
+      assert_eq!(1000, aapl.volume);
+      assert_eq!(200.5, quotes[1].as_ref().unwrap().price);
+   });
+}
+
+#[test]
+fn stream_decode_error() {
+   //! Ensure that a frame that can't be decoded doesn't end the stream
+
+   block_on(async {
+      // GIVEN - a server that sends garbage followed by a quote
+      let server = MockStreamServer::start(vec![vec![Step::Receive, Step::Send("!!!".to_string()), frame("AAPL", 150.0), Step::Close]]).await;
+
+      // WHEN - we stream
+      let quotes: Vec<_> = streamer(&server, vec!["AAPL"]).stream().await.unwrap().collect().await;
+
+      // THEN - the garbage is an error and the quote still comes through
+      assert_eq!(2, quotes.len());
+      assert!(quotes[0].is_err());
+      assert_eq!("AAPL", quotes[1].as_ref().unwrap().symbol);
+   });
+}
+
+#[test]
+fn stream_connection_lost() {
+   //! Ensure that losing the connection is an error at the end of the stream
+
+   block_on(async {
+      // GIVEN - a server that drops the connection after a quote
+      let server = MockStreamServer::start(vec![vec![Step::Receive, frame("AAPL", 150.0), Step::Drop]]).await;
+
+      // WHEN - we stream as ticks
+      let ticks: Vec<_> = streamer(&server, vec!["AAPL"]).stream_ticks().await.unwrap().collect().await;
+
+      // THEN - the quote is followed by the failure rather than a panic
+      assert_eq!(2, ticks.len());
+      assert!(ticks[0].is_ok());
+      assert!(format!("{:?}", ticks[1]).contains("StreamProtocol"));
+   });
+}
+
+#[test]
+fn stream_ping() {
+   //! Ensure that pings from Yahoo! are answered
+
+   block_on(async {
+      // GIVEN - a server that pings
+      let server = MockStreamServer::start(vec![vec![Step::Receive, Step::Ping, Step::Receive, frame("AAPL", 150.0), Step::Close]]).await;
+
+      // WHEN - we stream
+      let quotes: Vec<_> = streamer(&server, vec!["AAPL"]).stream().await.unwrap().collect().await;
+
+      // THEN - we answered the ping and kept streaming
+      assert_eq!(1, server.pongs());
+      assert_eq!(1, quotes.len());
+   });
+}
+
+#[test]
+#[should_panic(expected = "StreamConnect")]
+fn stream_connect_failed() {
+   //! Ensure that failing to connect is an error
+
+   block_on(async {
+      // GIVEN - a server that refuses connections
+      let server = MockStreamServer::start(Vec::new()).await;
+
+      // WHEN - we stream
+      let result = streamer(&server, vec!["AAPL"]).stream().await;
+
+      // THEN - we get an error
+      result.map(|_| ()).unwrap();
+   });
+}
+
+#[test]
+fn stream_reconnect() {
+   //! Ensure that a lost connection is reconnected and resubscribed
+
+   block_on(async {
+      // GIVEN - a server that drops the first connection
+      let server = MockStreamServer::start(vec![
+         vec![Step::Receive, frame("AAPL", 150.0), Step::Drop],
+         vec![Step::Receive, frame("AAPL", 151.0), Step::Close]
+      ]).await;
+
+      // WHEN - we stream with reconnects
+      let streamer = streamer(&server, vec!["AAPL"]).reconnect(reconnect());
+      let states = streamer.connection_states();
+      let quotes: Vec<_> = streamer.stream().await.unwrap().filter_map(|quote| async move { quote.ok() }).collect().await;
+
+      // THEN - we get the quotes from both connections
+      assert_eq!(2, quotes.len());
+      assert_eq!(150.0, quotes[0].price);
+      assert_eq!(151.0, quotes[1].price);
+
+      // AND - we subscribed again after reconnecting
+      assert_eq!(2, server.received().len());
+      assert_eq!(server.received()[0], server.received()[1]);
+
+      // AND - we saw the connection go down and come back - then give up once
+      // the server stops accepting connections
+      drop(streamer);
+      let states: Vec<_> = states.collect().await;
+      assert_eq!(vec![
+         ConnectionState::Connected,
+         ConnectionState::Reconnecting { attempt: 1 },
+         ConnectionState::Connected,
+         ConnectionState::Reconnecting { attempt: 1 },
+         ConnectionState::Reconnecting { attempt: 2 },
+         ConnectionState::GaveUp
+      ], states);
+   });
+}
+
+#[test]
+fn stream_reconnect_gives_up() {
+   //! Ensure that the stream ends once the reconnect policy runs out of attempts
+
+   block_on(async {
+      // GIVEN - a server that drops the only connection it accepts
+      let server = MockStreamServer::start(vec![vec![Step::Receive, frame("AAPL", 150.0), Step::Drop]]).await;
+
+      // WHEN - we stream with a policy that allows no reconnect attempts
+      let policy = ReconnectPolicy { max_attempts: Some(0), ..reconnect() };
+      let streamer = streamer(&server, vec!["AAPL"]).reconnect(policy);
+      let states = streamer.connection_states();
+      let quotes: Vec<_> = streamer.stream().await.unwrap().collect().await;
+
+      // THEN - we get the quote and the failure before the stream ends
+      assert_eq!(150.0, quotes[0].as_ref().unwrap().price);
+      assert!(quotes.last().unwrap().is_err());
+
+      // AND - we gave up without trying to reconnect
+      drop(streamer);
+      let states: Vec<_> = states.collect().await;
+      assert_eq!(vec![ConnectionState::Connected, ConnectionState::GaveUp], states);
+   });
+}
+
+#[test]
+fn stream_without_reconnect() {
+   //! Ensure that the stream ends with the connection when not reconnecting
+
+   block_on(async {
+      // GIVEN - a server that drops the connection
+      let server = MockStreamServer::start(vec![vec![Step::Receive, frame("AAPL", 150.0), Step::Drop], vec![Step::Receive]]).await;
+
+      // WHEN - we stream without reconnects
+      let quotes: Vec<_> = streamer(&server, vec!["AAPL"]).stream().await.unwrap().collect().await;
+
+      // THEN - we only get the quotes from the first connection
+      assert_eq!(150.0, quotes[0].as_ref().unwrap().price);
+      assert_eq!(1, server.received().len());
+   });
+}
+
+#[test]
+fn stream_subscriptions() {
+   //! Ensure that symbols can be changed while streaming
+
+   block_on(async {
+      // GIVEN - a server that waits for subscription changes
+      let server = MockStreamServer::start(vec![vec![Step::Receive, Step::Receive, Step::Receive, frame("MSFT", 200.0), Step::Close]]).await;
+
+      // WHEN - we change the symbols while streaming
+      let streamer = streamer(&server, vec!["AAPL"]);
+      let handle = streamer.handle();
+      let quotes = streamer.stream().await.unwrap();
+      handle.subscribe(&["MSFT", "AAPL"]);
+      handle.unsubscribe(&["AAPL"]);
+      let quotes: Vec<_> = quotes.collect().await;
+
+      // THEN - only the changes are sent to Yahoo!
+      assert_eq!(vec![
+         r#"{"subscribe":["AAPL"]}"#.to_string(),
+         r#"{"subscribe":["MSFT"]}"#.to_string(),
+         r#"{"unsubscribe":["AAPL"]}"#.to_string()
+      ], server.received());
+
+      // AND - the handle knows what we're subscribed to
+      assert_eq!(vec!["MSFT".to_string()], handle.symbols());
+      assert_eq!(1, quotes.len());
+   });
+}
+
+#[test]
+fn stream_subscriptions_reconnect() {
+   //! Ensure that symbol changes are kept when reconnecting
+
+   block_on(async {
+      // GIVEN - a server that drops the connection after a subscription change
+      let server = MockStreamServer::start(vec![
+         vec![Step::Receive, Step::Receive, Step::Drop],
+         vec![Step::Receive, frame("MSFT", 200.0), Step::Close]
+      ]).await;
+
+      // WHEN - we add a symbol - along with ones we're already subscribed to - while streaming with reconnects
+      let streamer = streamer(&server, vec!["AAPL"]).reconnect(reconnect());
+      let handle = streamer.handle();
+      let quotes = streamer.stream().await.unwrap();
+      handle.subscribe(&["AAPL", "MSFT"]);
+      handle.subscribe(&["MSFT"]);
+      let quotes: Vec<_> = quotes.filter_map(|quote| async move { quote.ok() }).collect().await;
+
+      // THEN - only the new symbol was sent, and the new connection subscribed to both
+      assert_eq!(vec![
+         r#"{"subscribe":["AAPL"]}"#.to_string(),
+         r#"{"subscribe":["MSFT"]}"#.to_string(),
+         r#"{"subscribe":["AAPL","MSFT"]}"#.to_string()
+      ], server.received());
+      assert_eq!("MSFT", quotes[0].symbol);
+   });
+}
+
+#[test]
+fn stream_ticks() {
+   //! Ensure that ticks have everything Yahoo! sent
+
+   block_on(async {
+      // GIVEN - a server that sends a full update followed by one that's back to the previous close
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive,
+         Step::Send(FrameBuilder::new("AAPL").price(150.0).change(1.5, 1.01).bid(149.5, 3).ask(150.5, 5).day_volume(1000).build()),
+         Step::Send(FrameBuilder::new("AAPL").price(148.5).build()),
+         Step::Close
+      ]]).await;
+
+      // WHEN - we stream ticks
+      let ticks: Vec<_> = streamer(&server, vec!["AAPL"]).stream_ticks().await.unwrap().collect().await;
+
+      // THEN - the full update has everything
+      assert_eq!(2, ticks.len());
+      let tick = ticks[0].as_ref().unwrap();
+      assert_eq!(Some(1.5), tick.change);
+      assert_eq!(Some(149.5), tick.bid);
+      assert_eq!(Some(3), tick.bid_size);
+      assert_eq!(Some(150.5), tick.ask);
+      assert_eq!(Some(5), tick.ask_size);
+      assert_eq!(Some(1000), tick.day_volume);
+
+      // AND - values that weren't sent are missing
+      let tick = ticks[1].as_ref().unwrap();
+      assert_eq!(None, tick.bid);
+      assert_eq!(None, tick.day_volume);
+      assert_eq!(None, tick.change);
+      assert_eq!(None, tick.change_percent);
+   });
+}
+
+#[test]
+fn stream_typed() {
+   //! Ensure that typed ticks carry the details for their kind of security
+
+   block_on(async {
+      // GIVEN - a server that sends a put, a cryptocurrency and an equity
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive,
+         Step::Send(FrameBuilder::new("AAPL200918P00140000").price(2.5).quote_type(QuoteType::Option).build()),
+         Step::Send(FrameBuilder::new("BTC-USD").price(10_000.0).quote_type(QuoteType::Cryptocurrency).build()),
+         Step::Send(FrameBuilder::new("AAPL").price(150.0).quote_type(QuoteType::Equity).build()),
+         Step::Close
+      ]]).await;
+
+      // WHEN - we stream typed ticks
+      let ticks: Vec<_> = streamer(&server, vec!["AAPL200918P00140000", "BTC-USD", "AAPL"]).stream_typed().await.unwrap().collect().await;
+
+      // THEN - each tick has the right kind
+      assert_eq!(3, ticks.len());
+      match ticks[0].as_ref().unwrap() {
+         TypedTick::Option { tick, option } => {
+            assert_eq!(2.5, tick.price);
+            assert_eq!(Some(OptionKind::Put), option.kind);
+         },
+         other => panic!("expected an option - got {:?}", other)
+      }
+      match ticks[1].as_ref().unwrap() {
+         TypedTick::Crypto { tick, .. } => assert_eq!("BTC-USD", tick.symbol),
+         other => panic!("expected a cryptocurrency - got {:?}", other)
+      }
+      match ticks[2].as_ref().unwrap() {
+         TypedTick::Other(tick) => assert_eq!(Some(QuoteType::Equity), tick.quote_type),
+         other => panic!("expected an equity - got {:?}", other)
+      }
+
+      // AND - every kind gives access to the common values
+      assert!(ticks.iter().all(|tick| tick.as_ref().unwrap().tick().price > 0.0));
+   });
+}
+
+#[test]
+fn stream_typed_option_kind() {
+   //! Ensure that an option's kind is only known when Yahoo! says what it is
+
+   block_on(async {
+      // GIVEN - a server that sends an option that only the symbol says is a call, and one nothing says anything about
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive,
+         Step::Send(FrameBuilder::new("AAPL200918C00150000").price(5.0).quote_type(QuoteType::Option).build()),
+         Step::Send(FrameBuilder::new("AAPL_OPTION").price(5.0).quote_type(QuoteType::Option).build()),
+         Step::Close
+      ]]).await;
+
+      // WHEN - we stream typed ticks
+      let ticks: Vec<_> = streamer(&server, vec!["AAPL200918C00150000", "AAPL_OPTION"]).stream_typed().await.unwrap().collect().await;
+
+      // THEN - the kind comes from the symbol, or is missing
+      let kinds: Vec<_> = ticks.iter().map(|tick| match tick.as_ref().unwrap() {
+         TypedTick::Option { option, .. } => option.kind,
+         other => panic!("expected an option - got {:?}", other)
+      }).collect();
+      assert_eq!(vec![Some(OptionKind::Call), None], kinds);
+   });
+}
+
+#[test]
+fn stream_record() {
+   //! Ensure that a recorded stream replays the same quotes
+
+   let path = std::env::temp_dir().join(format!("yahoo-finance-record-{}.rec", std::process::id()));
+
+   block_on(async {
+      // GIVEN - a recording of a stream
+      let server = MockStreamServer::start(vec![vec![Step::Receive, frame("AAPL", 150.0), frame("MSFT", 200.0), Step::Close]]).await;
+      let streamer = streamer(&server, vec!["AAPL", "MSFT"]).record(Recorder::create(&path).unwrap());
+      let streamed: Vec<_> = streamer.stream().await.unwrap().collect().await;
+
+      // WHEN - we replay it
+      let replayed: Vec<_> = Replay::new(&path).speed(ReplaySpeed::AsFastAsPossible).stream().await.unwrap().collect().await;
+
+      // THEN - we get the same quotes
+      assert_eq!(2, replayed.len());
+      for (streamed, replayed) in streamed.iter().zip(replayed.iter()) {
+         let (streamed, replayed) = (streamed.as_ref().unwrap(), replayed.as_ref().unwrap());
+         assert_eq!(streamed.symbol, replayed.symbol);
+         assert_eq!(streamed.price, replayed.price);
+      }
+   });
+
+   let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn stream_stop() {
+   //! Ensure that stopping ends the stream right away
+
+   block_on(async {
+      // GIVEN - a server that sends a quote and then goes quiet
+      let server = MockStreamServer::start(vec![vec![Step::Receive, frame("AAPL", 150.0)]]).await;
+      let streamer = streamer(&server, vec!["AAPL"]);
+      let handle = streamer.handle();
+      let mut quotes = streamer.stream().await.unwrap();
+      assert!(quotes.next().await.unwrap().is_ok());
+
+      // WHEN - we stop from the handle
+      handle.stop();
+
+      // THEN - the stream ends without waiting for another message
+      let end = tokio::time::timeout(Duration::from_secs(2), quotes.next()).await;
+      assert!(end.expect("the stream should end promptly").is_none());
+   });
+}
+
+#[test]
+fn stream_stop_when() {
+   //! Ensure that a future can stop the stream
+
+   block_on(async {
+      // GIVEN - a server that goes quiet and a signal to stop on
+      let server = MockStreamServer::start(vec![vec![Step::Receive]]).await;
+      let streamer = streamer(&server, vec!["AAPL"]);
+      let (stop, signal) = futures::channel::oneshot::channel::<()>();
+      streamer.stop_when(async { let _ = signal.await; });
+      let quotes = streamer.stream().await.unwrap();
+
+      // WHEN - the signal fires
+      stop.send(()).unwrap();
+
+      // THEN - the stream ends
+      let quotes = tokio::time::timeout(Duration::from_secs(2), quotes.collect::<Vec<_>>()).await;
+      assert!(quotes.expect("the stream should end promptly").is_empty());
+   });
+}
+
+#[test]
+fn stream_stop_reconnecting() {
+   //! Ensure that stopping interrupts waiting to reconnect
+
+   block_on(async {
+      // GIVEN - a streamer waiting a long time to reconnect
+      let server = MockStreamServer::start(vec![vec![Step::Receive, Step::Close]]).await;
+      let policy = ReconnectPolicy { initial_backoff: Duration::from_secs(60), ..reconnect() };
+      let streamer = streamer(&server, vec!["AAPL"]).reconnect(policy);
+      let mut states = streamer.connection_states();
+      let quotes = streamer.stream().await.unwrap();
+      states.next().await;
+      assert_eq!(Some(ConnectionState::Reconnecting { attempt: 1 }), states.next().await);
+
+      // WHEN - we stop
+      streamer.stop();
+
+      // THEN - the stream ends without waiting for the reconnect
+      let quotes = tokio::time::timeout(Duration::from_secs(2), quotes.collect::<Vec<_>>()).await;
+      assert!(quotes.is_ok());
+   });
+}
+
+#[test]
+fn stream_stale() {
+   //! Ensure that a silent connection is an error
+
+   block_on(async {
+      // GIVEN - a server that sends a quote and then goes quiet
+      let server = MockStreamServer::start(vec![vec![Step::Receive, frame("AAPL", 150.0)]]).await;
+
+      // WHEN - we stream with a stale timeout
+      let streamer = streamer(&server, vec!["AAPL"]).stale_timeout(Duration::from_millis(200));
+      let quotes: Vec<_> = streamer.stream().await.unwrap().collect().await;
+
+      // THEN - the quote is followed by an error and the stream ends
+      assert_eq!(2, quotes.len());
+      assert!(quotes[0].is_ok());
+      assert!(format!("{:?}", quotes[1]).contains("StreamStale"));
+   });
+}
+
+#[test]
+fn stream_stale_reconnect() {
+   //! Ensure that a silent connection is reconnected
+
+   block_on(async {
+      // GIVEN - a server that goes quiet on the first connection
+      let server = MockStreamServer::start(vec![
+         vec![Step::Receive],
+         vec![Step::Receive, frame("AAPL", 150.0), Step::Close]
+      ]).await;
+
+      // WHEN - we stream with a stale timeout and reconnects
+      let streamer = streamer(&server, vec!["AAPL"]).stale_timeout(Duration::from_millis(200)).reconnect(reconnect());
+      let quotes: Vec<_> = streamer.stream().await.unwrap().filter_map(|quote| async move { quote.ok() }).collect().await;
+
+      // THEN - we get the quote from the new connection
+      assert_eq!(1, quotes.len());
+      assert!(streamer.stats().reconnects >= 1);
+   });
+}
+
+#[test]
+fn stream_stats() {
+   //! Ensure that the health of the connection is tracked
+
+   block_on(async {
+      // GIVEN - a server that sends a heartbeat, a quote and garbage
+      let server = MockStreamServer::start(vec![vec![
+         Step::Receive, Step::Send(FrameBuilder::heartbeat().build()), frame("AAPL", 150.0), Step::Send("!!!".to_string()), Step::Close
+      ]]).await;
+
+      // WHEN - we stream
+      let streamer = streamer(&server, vec!["AAPL"]);
+      let quotes: Vec<_> = streamer.stream().await.unwrap().collect().await;
+
+      // THEN - the heartbeat isn't a quote
+      assert_eq!(2, quotes.len());
+      assert_eq!("AAPL", quotes[0].as_ref().unwrap().symbol);
+
+      // AND - everything was counted
+      let stats = streamer.stats();
+      assert_eq!(3, stats.messages);
+      assert_eq!(1, stats.heartbeats);
+      assert_eq!(1, stats.decode_failures);
+      assert_eq!(0, stats.reconnects);
+      assert!(stats.last_message.is_some());
+      assert!(stats.last_updates.contains_key("AAPL"));
+   });
+}